@@ -1,9 +1,18 @@
 use std::time::Duration;
 
 use async_std::task::sleep;
-use bevy::{ ecs::world::{ Command, CommandQueue }, prelude::*, tasks::AsyncComputeTaskPool };
-
-use bevy_lazy_signals::{ api::LazySignals, LazySignalsPlugin, StaticStrRef };
+use bevy::{
+    ecs::world::{ Command, CommandQueue },
+    prelude::*,
+    tasks::{ futures_lite::StreamExt, AsyncComputeTaskPool },
+};
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Signal, SignalReader },
+    framework::{ LazySignalsResult, TaskOptions, TaskRetriggerPolicy },
+    LazySignalsPlugin,
+    StaticStrRef,
+};
 
 // simple resource to simulate a service that tracks whether a user is logged in or not
 #[derive(Resource, Default)]
@@ -22,27 +31,30 @@ impl MyExampleAuthResource {
 
 // simple command to toggle the login status of the user
 struct MyToggleLoginCommand {
-    entity: Entity,
+    signal: Signal<bool>,
 }
 
 impl Command for MyToggleLoginCommand {
     fn apply(self, world: &mut World) {
         info!("Toggling login");
-        if let Some(Ok(status)) = LazySignals.read::<bool>(self.entity, world) {
-            LazySignals.send(self.entity, !status, &mut world.commands());
+        if let Some(Ok(status)) = LazySignals.read(self.signal, world) {
+            LazySignals.send(self.signal, !status, &mut world.commands());
             world.flush_commands();
             info!("...toggled");
         }
     }
 }
 
-// this just keeps track of all the LazySignals primitives. just need the entity.
+// this just keeps track of all the LazySignals primitives. just need the entity for most of them.
+// the handles we use later are kept separately since each one knows its own data type
 #[derive(Resource, Default)]
 struct MyTestResource {
     pub computed: Vec<Entity>,
     pub effect: Vec<Entity>,
     pub signal: Vec<Entity>,
     pub task: Vec<Entity>,
+    pub logged_in: Option<Signal<bool>>,
+    pub retrigger: Option<Signal<()>>,
 }
 
 // concrete tuple type to safely work with the DynamicTuple coming out of the LazySignals systems
@@ -51,6 +63,9 @@ type MyClosureArgs = (Option<bool>, Option<StaticStrRef>);
 // making an alias to make it easier to read code in some places
 type MyAuthArgs = MyClosureArgs;
 
+// same as above, but for things that depend on the (owned) message computed from MyAuthArgs
+type MyLoginMsgArgs = (Option<bool>, Option<String>);
+
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
@@ -73,7 +88,8 @@ fn main() {
 }
 
 fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
-    // create a signal (you need to register data types if not bool, i32, f64, or &'static str)
+    // create a signal (you need to register data types if not bool, u32, f64, &'static str, (),
+    // String, a Vec of those, or a HashMap from String to bool, u32, f64, or String)
     // (see LazySignalsPlugin)
 
     // this will reflect a LazySignalsState<T> type based on the first parameter type
@@ -85,12 +101,13 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
 
     // leave signals and computeds as local values to use as deps throughout the init system
     // since we can't move the deps into our closures from the test resource
-    test.signal.push(signal0);
+    test.signal.push(signal0.into());
+    test.logged_in = Some(signal0);
     info!("created test signal 0, entity {:#?}", test.signal[0]);
 
-    // for strings the only thing I've gotten to work so far is &'static str
+    // string literals can be &'static str, anything built at runtime can be a String
     let signal1 = LazySignals.state("Congrats, you logged in somehow", &mut commands);
-    test.signal.push(signal1);
+    test.signal.push(signal1.into());
     info!("created test signal 1, entity {:#?}", test.signal[1]);
 
     // for an effect trigger, we don't care about the value, only that it changed
@@ -101,7 +118,8 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
     // there's also a way to send a regular signal as a trigger but beware: that is a good recipe
     // for an update storm
     let signal2 = LazySignals.state((), &mut commands);
-    test.signal.push(signal2);
+    test.signal.push(signal2.into());
+    test.retrigger = Some(signal2);
     info!("created test signal 2, entity {:#?}", test.signal[2]);
 
     // simple effect that logs its sources whenever one changes or it is triggered
@@ -132,13 +150,12 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
                 }
             },
             // type of each source must match type at same tuple position
-            // it's not unsafe(?); it just won't work if we screw this up
-            // TODO definitely think about that some more
-            vec![signal0, signal1], // sending either signal triggers the effect
+            // if we screw this up, the effect is refused with an ArgsTypeMismatch error
+            vec![signal0.into(), signal1.into()], // sending either signal triggers the effect
             // explicit triggers are not added to the args tuple like sources are
             Vec::<Entity>::new(),
             &mut commands
-        )
+        ).into()
     );
 
     info!("created test effect 0, entity {:#?}", test.effect[0]);
@@ -156,17 +173,14 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
         // (Option<bool>, Option<&str>) are interchangeable when defining computeds and effects
 
         // default error message (only if args.0 == false)
-        let mut value = "You are not authorized to view this";
+        let mut value = String::from("You are not authorized to view this");
 
         // if logged_in
         // (Err or None will return Err or None, this block runs only if args.0 == true)
         if args.0? {
             // show a logged in message, if one exists
-            if let Some(msg) = args.1 {
-                value = msg;
-            } else {
-                value = "Greetings, Starfighter";
-            }
+            // the result is a String so it can be built at runtime
+            value = format!("{} (logged in)", args.1.unwrap_or("Greetings, Starfighter"));
 
             // could also just do: let value = args.1?;
             // and bubble the error up as a None return value
@@ -179,17 +193,17 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
     };
 
     // simple computed to store the string value or an error, depending on the bool
-    let computed0 = LazySignals.computed::<MyAuthArgs, StaticStrRef>(
+    let computed0 = LazySignals.computed::<MyAuthArgs, String>(
         derive_login_msg,
-        vec![signal0, signal1], // sending either signal triggers a recompute
+        vec![signal0.into(), signal1.into()], // sending either signal triggers a recompute
         &mut commands
     );
-    test.computed.push(computed0);
+    test.computed.push(computed0.into());
     info!("created test computed 0, entity {:#?}", test.computed[0]);
 
     // set this one up to trigger from a memo instead of a signal
     test.effect.push(
-        LazySignals.effect::<MyAuthArgs>(
+        LazySignals.effect::<MyLoginMsgArgs>(
             // closure to call when the effect is triggered
             |args, _world| {
                 // second effect, same as the first, but use the memo as the string instead of the signal
@@ -204,13 +218,35 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
                     info!("EFFECT2: got logged_in_msg: {} from args", logged_in_msg);
                 }
             },
-            vec![signal0, computed0],
+            vec![signal0.into(), computed0.into()],
             vec![],
             &mut commands
-        )
+        ).into()
     );
     info!("created test effect 1, entity {:#?}", test.effect[1]);
 
+    // an effect can also be a regular Bevy system that takes the source values as its input
+    test.effect.push(
+        LazySignals.effect_system::<(Option<bool>,), _>(
+            log_login_status,
+            vec![signal0.into()],
+            vec![],
+            &mut commands
+        ).into()
+    );
+    info!("created test effect 2, entity {:#?}", test.effect[2]);
+
+    // a computed can also be a read-only Bevy system, so it can read resources and components
+    // it recomputes when a source changes or when anything it reads is changed
+    test.computed.push(
+        LazySignals.computed_system::<(Option<StaticStrRef>,), StaticStrRef, _, _>(
+            derive_auth_msg,
+            vec![signal1.into()],
+            &mut commands
+        ).into()
+    );
+    info!("created test computed 1, entity {:#?}", test.computed[1]);
+
     // test a long-running async task with triggers only and no sources (pass in unit type)
 
     // there's no reason a task can't take args. the closure fn sig is the same as an effect except
@@ -220,7 +256,7 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
     // and the task has exited
 
     test.task.push(
-        LazySignals.task::<()>(
+        LazySignals.task_with::<()>(
             // closure to call when triggered
             move |_args| {
                 let thread_pool = AsyncComputeTaskPool::get();
@@ -244,7 +280,7 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
                     // all the tasks that return their commands in the same tick would then send
                     // the same signal, which would update the LazySignalsState component with a
                     // next_value several times, but only result in sending the signal once
-                    command_queue.push(MyToggleLoginCommand { entity: signal0 });
+                    command_queue.push(MyToggleLoginCommand { signal: signal0 });
 
                     command_queue
                 })
@@ -252,18 +288,63 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
             Vec::<Entity>::new(),
             // triggering a signal will run effects without passing the signal's value as a param
             // (it still sends the value of the sources as usual, although this effect has none)
-            vec![signal2],
+            vec![signal2.into()],
+            TaskOptions {
+                // ignore triggers while the task is still running (could also queue or restart)
+                policy: TaskRetriggerPolicy::Drop,
+                // give up on any run that takes longer than this (like a network request would)
+                timeout: Some(Duration::from_secs(30)),
+            },
             &mut commands
-        )
+        ).into()
     );
     info!("created test task 0, entity {:#?}", test.task[0]);
 
+    // a streaming task gets a sender to push partial results (e.g. progress) while it runs
+    let progress = LazySignals.state(0.0, &mut commands);
+    test.signal.push(progress.into());
+    info!("created test signal 3, entity {:#?}", test.signal[3]);
+
+    test.task.push(
+        LazySignals.streaming_task::<(), f64>(
+            |_args, progress| {
+                AsyncComputeTaskPool::get().spawn(async move {
+                    for step in 1..=10 {
+                        sleep(Duration::from_millis(500)).await;
+                        // the signal is sent the next time the LazySignals systems run
+                        if progress.send(f64::from(step) / 10.0).is_err() {
+                            break;
+                        }
+                    }
+                    info!("TASK1: done");
+                    CommandQueue::default()
+                })
+            },
+            progress,
+            Vec::<Entity>::new(),
+            vec![signal2.into()],
+            TaskOptions::default(),
+            &mut commands
+        ).into()
+    );
+    info!("created test task 1, entity {:#?}", test.task[1]);
+
+    // async code can also await changes to a signal as a Stream instead of polling it
+    let mut logins = LazySignals.watch(signal0, &mut commands);
+    AsyncComputeTaskPool::get()
+        .spawn(async move {
+            while let Some(logged_in) = logins.next().await {
+                info!("WATCH0: logged in is now {:?}", logged_in);
+            }
+        })
+        .detach();
+
     // simple computed to store a string value or an error, depending on the bool
     test.computed.push(
-        LazySignals.computed::<MyAuthArgs, StaticStrRef>(
+        LazySignals.computed::<MyLoginMsgArgs, String>(
             |args| {
                 // default error message
-                let mut value = "You are not authorized to view this";
+                let mut value = String::from("You are not authorized to view this");
 
                 // if logged_in
                 if let Some(logged_in) = args.0 {
@@ -275,18 +356,72 @@ fn init(mut test: ResMut<MyTestResource>, mut commands: Commands) {
                     }
                 }
 
-                info!("COMPUTED1 value: {}", value);
+                info!("COMPUTED2 value: {}", value);
                 Some(Ok(value))
             },
-            vec![signal0, computed0],
+            vec![signal0.into(), computed0.into()],
             &mut commands
-        )
+        ).into()
     );
-    info!("created test computed 2, entity {:#?}", test.computed[1]);
+    info!("created test computed 2, entity {:#?}", test.computed[2]);
+
+    // an async computed runs a future whenever a source changes and keeps its progress as an
+    // AsyncValue (Idle, Loading, Ready, or Failed), no need to write a command to store the result
+    // (LazySignalsAsyncValue<&'static str> is already registered, other results need registering)
+    test.computed.push(
+        LazySignals.async_computed::<(Option<bool>,), StaticStrRef, StaticStrRef, _>(
+            |args| async move {
+                // pretend to look up the profile of the user
+                sleep(Duration::from_secs(1)).await;
+                match args.0 {
+                    Some(true) => Ok("Profile loaded"),
+                    _ => Err("Not logged in"),
+                }
+            },
+            vec![signal0.into()],
+            &mut commands
+        ).into()
+    );
+    info!("created test computed 3, entity {:#?}", test.computed[3]);
+
+    // a tracked computed finds its own sources: whatever it read through the context last time
+    // (while logged out it only depends on signal0, so a new login message does not recompute it)
+    test.computed.push(
+        LazySignals.tracked_computed(
+            move |cx| {
+                let value = match cx.get(signal0)? {
+                    true => cx.get(computed0)?,
+                    false => String::from("Nothing to see here"),
+                };
+                info!("COMPUTED4 value: {}", value);
+                Some(Ok(value))
+            },
+            &mut commands
+        ).into()
+    );
+    info!("created test computed 4, entity {:#?}", test.computed[4]);
 
     info!("init complete");
 }
 
+// the system params are whatever the effect needs (no exclusive world access required)
+fn log_login_status(In(args): In<(Option<bool>,)>, auth: Res<MyExampleAuthResource>) {
+    info!("EFFECT3: got {:?} from args.0, resource says {}", args.0, auth.is_logged_in());
+}
+
+// the resource is read by the system, so changing it recomputes the memo without a signal
+fn derive_auth_msg(
+    In(args): In<(Option<StaticStrRef>,)>,
+    auth: Res<MyExampleAuthResource>
+) -> LazySignalsResult<StaticStrRef> {
+    let value = match auth.is_logged_in() {
+        true => args.0.unwrap_or("Greetings, Starfighter"),
+        false => "You are not authorized to view this",
+    };
+    info!("COMPUTED1 value: {}", value);
+    Some(Ok(value))
+}
+
 fn send_some_signals(test: Res<MyTestResource>, mut commands: Commands) {
     /* uncomment this to automatically log the user back in on the next tick after logging out
     trace!("sending 'true' to {:?}", test.signal1);
@@ -295,26 +430,31 @@ fn send_some_signals(test: Res<MyTestResource>, mut commands: Commands) {
 
     // even though this runs every tick, the task will trigger once, then run until it exits
     // before being eligible to run again
-    trace!("triggering {:?}", test.signal[2]);
-    LazySignals.trigger(test.signal[2], &mut commands);
+    if let Some(retrigger) = test.retrigger {
+        trace!("triggering {:?}", retrigger);
+        LazySignals.trigger(retrigger, &mut commands);
+    }
 }
 
+// SignalReader reads through a query, so this does not need exclusive world access
 fn status(
-    world: &World,
     example_auth_resource: Res<MyExampleAuthResource>,
-    test: Res<MyTestResource>
+    test: Res<MyTestResource>,
+    signals: SignalReader<bool>
 ) {
     trace!("logged in: {}", example_auth_resource.is_logged_in());
 
-    match LazySignals.read::<bool>(test.signal[0], world) {
-        Some(Ok(value)) => {
-            trace!("value: {}", value);
-        }
-        Some(Err(error)) => {
-            error!("error: {}", error);
-        }
-        None => {
-            trace!("None");
+    if let Some(logged_in) = test.logged_in {
+        match signals.read(logged_in) {
+            Some(Ok(value)) => {
+                trace!("value: {}", value);
+            }
+            Some(Err(error)) => {
+                error!("error: {}", error);
+            }
+            None => {
+                trace!("None");
+            }
         }
     }
 }
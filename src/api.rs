@@ -1,9 +1,34 @@
-use std::sync::Mutex;
+use std::{
+    any::TypeId,
+    collections::VecDeque,
+    fmt::{ Debug, Display, Formatter },
+    future::Future,
+    hash::{ Hash, Hasher },
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{ AtomicU64, Ordering },
+        mpsc::{ channel, Receiver, Sender, TryRecvError },
+        Arc,
+        Mutex,
+    },
+    task::{ Context, Poll, Waker },
+};
 
-use bevy::{ prelude::*, reflect::{ DynamicTuple, GetTupleField } };
+use bevy::{
+    ecs::{
+        component::{ ComponentId, Tick },
+        query::QueryEntityError,
+        system::{ BoxedSystem, ReadOnlySystem, SystemId, SystemParam },
+        world::CommandQueue,
+    },
+    prelude::*,
+    reflect::{ DynamicTuple, GetPath, GetTupleField, Tuple, TypeInfo },
+    tasks::{ futures_lite::Stream, AsyncComputeTaskPool },
+};
 
 use crate::{
-    commands::LazySignalsCommandsExt,
+    commands::{ LazySignalsCommandsExt, StoreAsyncValueCommand },
     framework::*,
     lazy_immutable::{ LazySignalsImmutable, LazySignalsState },
 };
@@ -15,13 +40,62 @@ pub fn get_field<T: LazySignalsData>(tuple: &DynamicTuple, index: usize) -> Opti
     tuple.get_field::<T>(index) // returns None if type doesn't match
 }
 
+pub fn make_async_computed_with<P: LazySignalsArgs, R: LazySignalsData, E: Display, F>(
+    computed: Entity,
+    closure: impl AsyncComputed<P, F>
+) -> Mutex<Box<dyn EffectWrapper>>
+    where F: Future<Output = Result<R, E>> + Send + 'static
+{
+    // bumped on each run so results from superseded runs can be recognized and thrown away
+    let latest = Arc::new(AtomicU64::new(0));
+    Mutex::new(
+        Box::new(move |tuple, world| {
+            trace!("-running async computed with args {:?}", tuple);
+            let future = closure(make_tuple::<P>(tuple)?);
+            let generation = latest.fetch_add(1, Ordering::SeqCst) + 1;
+            let latest = latest.clone();
+            let task = AsyncComputeTaskPool::get().spawn(async move {
+                let value = match future.await {
+                    Ok(data) => AsyncValue::Ready(data),
+                    Err(error) => AsyncValue::Failed(error.to_string()),
+                };
+                let mut command_queue = CommandQueue::default();
+                command_queue.push(StoreAsyncValueCommand { computed, generation, latest, value });
+                command_queue
+            });
+
+            // the effect is borrowed from this entity while it runs, so defer the changes
+            let mut commands = world.commands();
+            commands.send_signal(computed, AsyncValue::<R>::Loading);
+
+            // replacing the RunningTask drops (cancels) the Task of the superseded run
+            commands.entity(computed).insert(RunningTask { task, deadline: None });
+            Ok(())
+        })
+    )
+}
+
 pub fn make_effect_with<P: LazySignalsArgs>(
     mut closure: impl Effect<P>
 ) -> Mutex<Box<dyn EffectWrapper>> {
     Mutex::new(
         Box::new(move |tuple, world| {
             trace!("-running effect context with args {:?}", tuple);
-            closure(make_tuple::<P>(tuple), world);
+            closure(make_tuple::<P>(tuple)?, world);
+            Ok(())
+        })
+    )
+}
+
+pub fn make_effect_system_with<P: LazySignalsArgs>(
+    system: SystemId<P>
+) -> Mutex<Box<dyn EffectWrapper>> {
+    Mutex::new(
+        Box::new(move |tuple, world| {
+            trace!("-running effect system with args {:?}", tuple);
+            world
+                .run_system_with_input(system, make_tuple::<P>(tuple)?)
+                .map_err(|_| LazySignalsError::SystemError(system.entity()))
         })
     )
 }
@@ -32,30 +106,291 @@ pub fn make_computed_with<P: LazySignalsArgs, R: LazySignalsData>(
     Mutex::new(
         Box::new(move |tuple, entity, world| {
             trace!("-running computed context with args {:?}", tuple);
-            let result = closure(make_tuple::<P>(tuple));
-            if let Some(Err(error)) = result {
-                // TODO process errors
-                error!("ERROR running computed: {}", error.to_string());
-            }
+            // any error is stored as the value and recorded by the system that ran this
+            let result = match make_tuple::<P>(tuple) {
+                Ok(args) => closure(args),
+                Err(error) => Some(Err(error)),
+            };
             store_result::<R>(result, entity, world)
         })
     )
 }
 
+/// A computed system is shared between the ComputedContext that runs it and the change detector
+/// that looks at what it reads and when it last ran.
+pub type SharedComputedSystem<P, R> = Arc<Mutex<BoxedSystem<P, LazySignalsResult<R>>>>;
+
+pub fn make_computed_system_with<P: LazySignalsArgs, R: LazySignalsData>(
+    system: SharedComputedSystem<P, R>
+) -> Mutex<Box<dyn ComputedContext>> {
+    Mutex::new(
+        Box::new(move |tuple, entity, world| {
+            trace!("-running computed system with args {:?}", tuple);
+            let result = match make_tuple::<P>(tuple) {
+                Ok(args) => system.lock().unwrap().run(args, world),
+                Err(error) => Some(Err(error)),
+            };
+            store_result::<R>(result, entity, world)
+        })
+    )
+}
+
+pub fn make_change_detector_with<P: LazySignalsArgs, R: LazySignalsData>(
+    system: SharedComputedSystem<P, R>
+) -> Box<dyn SystemChangeDetector> {
+    Box::new(move |world| {
+        let system = system.lock().unwrap();
+        let access = system.component_access();
+
+        // no way to tell what something like &World looks at, so just recompute every time
+        if access.has_read_all() {
+            return true;
+        }
+
+        let last_run = system.get_last_run();
+        let this_run = world.read_change_tick();
+        for component_id in access.reads() {
+            if is_changed(component_id, last_run, this_run, world) {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Whether the resource, or the component on any entity, changed between the two ticks.
+/// Like a Changed<T> filter, this compares the change ticks of each entity that has the component
+/// and stops at the first one that is newer. (removing a component or despawning an entity does
+/// not count)
+///
+/// This looks at every entity that has the component, not just the ones the system's queries
+/// match, so each frame it costs up to one tick comparison per read component per entity with
+/// that component, for each computed system. Fine for components on a handful of entities, but a
+/// computed system that reads something like Transform is better off with a plain source signal.
+fn is_changed(component_id: ComponentId, last_run: Tick, this_run: Tick, world: &World) -> bool {
+    if let Some(ticks) = world.get_resource_change_ticks_by_id(component_id) {
+        return ticks.is_changed(last_run, this_run);
+    }
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| archetype.contains(component_id))
+        .flat_map(|archetype| archetype.entities())
+        .filter_map(|entity| world.entity(entity.id()).get_change_ticks_by_id(component_id))
+        .any(|ticks| ticks.is_changed(last_run, this_run))
+}
+
 pub fn make_task_with<P: LazySignalsArgs>(
     closure: impl AsyncTask<P>
 ) -> Mutex<Box<dyn TaskWrapper>> {
     Mutex::new(
         Box::new(move |tuple| {
             trace!("-running task context with args {:?}", tuple);
-            closure(make_tuple::<P>(tuple))
+            Ok(closure(make_tuple::<P>(tuple)?))
+        })
+    )
+}
+
+pub fn make_streaming_task_with<P: LazySignalsArgs, T: LazySignalsData>(
+    closure: impl StreamingTask<P, T>,
+    sender: SignalSender<T>
+) -> Mutex<Box<dyn TaskWrapper>> {
+    Mutex::new(
+        Box::new(move |tuple| {
+            trace!("-running streaming task context with args {:?}", tuple);
+            Ok(closure(make_tuple::<P>(tuple)?, sender.clone()))
+        })
+    )
+}
+
+pub fn make_tracked_computed_with<R: LazySignalsData>(
+    closure: impl TrackedComputed<R>,
+    tracked: Arc<Mutex<Vec<Entity>>>
+) -> Mutex<Box<dyn ComputedContext>> {
+    Mutex::new(
+        Box::new(move |_tuple, entity, world| {
+            trace!("-running tracked computed {:?}", entity);
+            let mut context = TrackingContext::new(world);
+            let result = closure(&mut context);
+            let mut read = context.into_sources();
+
+            // reading its own (previous) value does not make it its own source
+            read.retain(|source| source != entity);
+            *tracked.lock().unwrap() = read;
+            store_result::<R>(result, entity, world)
+        })
+    )
+}
+
+pub fn make_tracked_effect_with(
+    mut closure: impl TrackedEffect,
+    tracked: Arc<Mutex<Vec<Entity>>>
+) -> Mutex<Box<dyn EffectWrapper>> {
+    Mutex::new(
+        Box::new(move |_tuple, world| {
+            trace!("-running tracked effect");
+            let mut context = TrackingContext::new(world);
+            closure(&mut context);
+            *tracked.lock().unwrap() = context.into_sources();
+            Ok(())
+        })
+    )
+}
+
+pub fn make_receiver_with<T: LazySignalsData>(
+    signal: Entity,
+    receiver: Receiver<T>
+) -> Mutex<Box<dyn ReceiverWrapper>> {
+    Mutex::new(
+        Box::new(move |commands: &mut Commands| {
+            // the values would just overwrite each other's next_value, so only send the latest
+            let mut latest = None;
+            let connected = loop {
+                match receiver.try_recv() {
+                    Ok(data) => {
+                        latest = Some(data);
+                    }
+                    Err(TryRecvError::Empty) => {
+                        break true;
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        break false;
+                    }
+                }
+            };
+            if let Some(data) = latest {
+                trace!("-received a value for {:?}", signal);
+                commands.send_signal(signal, data);
+            }
+            connected
+        })
+    )
+}
+
+pub fn make_watcher_with<T: LazySignalsData>(
+    watched: Entity,
+    effect: Entity,
+    feeder: WatchFeeder<T>
+) -> Mutex<Box<dyn EffectWrapper>> {
+    Mutex::new(
+        Box::new(move |_tuple, world| {
+            let mut watch = feeder.watch.lock().unwrap();
+
+            // read the whole result since the args would turn any error into None
+            let state = world
+                .get_entity(watched)
+                .map(|entity| entity.get::<LazySignalsState<T>>());
+            let value = match state {
+                Some(Some(state)) => state.value(),
+                Some(None) => Some(Err(LazySignalsError::ReadError(watched))),
+                None => Some(Err(LazySignalsError::NoSignalError)),
+            };
+            trace!("-watcher {:?} got a value from {:?}", effect, watched);
+            watch.push(value);
+            Ok(())
+        })
+    )
+}
+
+fn make_watch_closer_with<T: LazySignalsData>(
+    watch: Arc<Mutex<Watch<T>>>
+) -> Box<dyn WatchCloser> {
+    Box::new(move |error| {
+        // the stream ends once the effect (and its WatchFeeder) is dropped right after this
+        watch.lock().unwrap().push(Some(Err(error)));
+    })
+}
+
+pub fn make_path_setter_with<T: LazySignalsData>(
+    target: Entity,
+    component: ReflectComponent,
+    field: String,
+    path: String
+) -> Mutex<Box<dyn EffectWrapper>> {
+    Mutex::new(
+        Box::new(move |tuple, world| {
+            trace!("-writing {} of {:?}", path, target);
+            let Some(data) = make_tuple::<(Option<T>,)>(tuple)?.0 else {
+                return Ok(());
+            };
+            let error = || LazySignalsError::InvalidPath(path.clone());
+            let mut entity = world.get_entity_mut(target).ok_or_else(error)?;
+            let mut reflected = component.reflect_mut(&mut entity).ok_or_else(error)?;
+
+            // don't trip change detection (e.g. of a mirror of the same field) if nothing changed
+            let current = reflected.reflect_path(field.as_str()).map_err(|_| error())?;
+            if current.reflect_partial_eq(&data) == Some(true) {
+                return Ok(());
+            }
+            reflected
+                .reflect_path_mut(field.as_str())
+                .map_err(|_| error())?
+                .set(Box::new(data))
+                .map_err(|_| error())
+        })
+    )
+}
+
+pub fn make_path_mirror_with<T: LazySignalsData>(
+    state: Entity,
+    target: Entity,
+    component: ReflectComponent,
+    component_type: TypeId,
+    field: String,
+    path: String
+) -> Mutex<Box<dyn MirrorWrapper>> {
+    let mut last_run = Tick::new(0);
+    Mutex::new(
+        Box::new(move |world: &World, commands: &mut Commands| {
+            let this_run = world.read_change_tick();
+            let entity = world.get_entity(target).ok_or(LazySignalsError::MissingSource(target))?;
+            let changed = world
+                .components()
+                .get_id(component_type)
+                .and_then(|component_id| entity.get_change_ticks_by_id(component_id))
+                .is_some_and(|ticks| ticks.is_changed(last_run, this_run));
+            if !changed {
+                return Ok(());
+            }
+            last_run = this_run;
+
+            let data = component
+                .reflect(entity)
+                .and_then(|reflected| reflected.reflect_path(field.as_str()).ok())
+                .and_then(T::from_reflect);
+            let data = data.ok_or_else(|| LazySignalsError::InvalidPath(path.clone()))?;
+            trace!("-mirroring {} of {:?}", path, target);
+            commands.send_signal(state, data);
+            Ok(())
         })
     )
 }
 
 /// Convenience function to convert DynamicTuples into a concrete type.
-pub fn make_tuple<T: LazySignalsArgs>(tuple: &DynamicTuple) -> T {
-    <T as FromReflect>::from_reflect(tuple).unwrap()
+/// If the types don't line up, the error describes the first field that doesn't match.
+pub fn make_tuple<T: LazySignalsArgs>(tuple: &DynamicTuple) -> Result<T, LazySignalsError> {
+    <T as FromReflect>::from_reflect(tuple).ok_or_else(|| {
+        let mut index = 0;
+        let mut expected = "nothing";
+        let mut found = "nothing";
+        if let TypeInfo::Tuple(info) = T::type_info() {
+            for i in 0..info.field_len().max(tuple.field_len()) {
+                let field = info.field_at(i);
+                let arg = tuple.field(i);
+                if field.map(|f| f.type_id()) != arg.map(|a| a.as_any().type_id()) {
+                    index = i;
+                    expected = field.map_or("nothing", |f| f.type_path());
+                    found = arg.map_or("nothing", |a| a.reflect_type_path());
+                    break;
+                }
+            }
+        }
+        LazySignalsError::ArgsTypeMismatch {
+            index,
+            expected: expected.to_string(),
+            found: found.to_string(),
+        }
+    })
 }
 
 /// Convenience function to store a result in an entity.
@@ -70,19 +405,438 @@ pub fn store_result<T: LazySignalsData>(
     component.update(data)
 }
 
+/// ## Typed handles
+/// Anything backed by a LazySignalsState<T> that can be read.
+pub trait Readable<T: LazySignalsData>: Copy {
+    /// The underlying entity, for use with the lower-level commands API.
+    fn entity(&self) -> Entity;
+}
+
+/// Handle to a state (LazySignalsState<T> that can be sent new values).
+pub struct Signal<T: LazySignalsData> {
+    entity: Entity,
+    data_type: PhantomData<T>,
+}
+
+impl<T: LazySignalsData> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: LazySignalsData> Copy for Signal<T> {}
+
+impl<T: LazySignalsData> Debug for Signal<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Signal").field(&self.entity).finish()
+    }
+}
+
+impl<T: LazySignalsData> PartialEq for Signal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity
+    }
+}
+
+impl<T: LazySignalsData> Eq for Signal<T> {}
+
+impl<T: LazySignalsData> Hash for Signal<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.entity.hash(state);
+    }
+}
+
+impl<T: LazySignalsData> Readable<T> for Signal<T> {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl<T: LazySignalsData> From<Signal<T>> for Entity {
+    fn from(signal: Signal<T>) -> Self {
+        signal.entity
+    }
+}
+
+/// Handle to a computed memo (LazySignalsState<T> that is only updated by its function).
+pub struct Memo<T: LazySignalsData> {
+    entity: Entity,
+    data_type: PhantomData<T>,
+}
+
+impl<T: LazySignalsData> Clone for Memo<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: LazySignalsData> Copy for Memo<T> {}
+
+impl<T: LazySignalsData> Debug for Memo<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Memo").field(&self.entity).finish()
+    }
+}
+
+impl<T: LazySignalsData> PartialEq for Memo<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity
+    }
+}
+
+impl<T: LazySignalsData> Eq for Memo<T> {}
+
+impl<T: LazySignalsData> Hash for Memo<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.entity.hash(state);
+    }
+}
+
+impl<T: LazySignalsData> Readable<T> for Memo<T> {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl<T: LazySignalsData> From<Memo<T>> for Entity {
+    fn from(memo: Memo<T>) -> Self {
+        memo.entity
+    }
+}
+
+/// Handle to an effect. There is no value to read or send.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EffectHandle(Entity);
+
+impl EffectHandle {
+    /// The underlying entity, for use with the lower-level commands API.
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+}
+
+impl From<EffectHandle> for Entity {
+    fn from(effect: EffectHandle) -> Self {
+        effect.0
+    }
+}
+
+/// Handle to a long-running task. There is no value to read or send.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TaskHandle(Entity);
+
+impl TaskHandle {
+    /// The underlying entity, for use with the lower-level commands API.
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+}
+
+impl From<TaskHandle> for Entity {
+    fn from(task: TaskHandle) -> Self {
+        task.0
+    }
+}
+
+/// ## Tracking
+/// Passed to a tracked computed or effect. Each signal or computed read with get becomes one of
+/// its sources. Like TC39 signals, only what was read on the last run counts, so a branch that
+/// did not run does not subscribe to anything.
+pub struct TrackingContext<'a> {
+    world: &'a mut World,
+    read: Vec<Entity>,
+    untracked: bool,
+}
+
+impl<'a> TrackingContext<'a> {
+    pub fn new(world: &'a mut World) -> Self {
+        Self {
+            world,
+            read: Vec::new(),
+            untracked: false,
+        }
+    }
+
+    /// Read the value and depend on it. Errors come back as None, the same as they do in args.
+    /// (an entity that no longer exists reads as None and is not depended on)
+    pub fn get<T: LazySignalsData>(&mut self, immutable: impl Readable<T>) -> Option<T> {
+        let entity = immutable.entity();
+        let exists = self.world.get_entity(entity).is_some();
+        if exists && !self.untracked && !self.read.contains(&entity) {
+            self.read.push(entity);
+        }
+        match LazySignals.value(immutable, self.world) {
+            Some(Ok(data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Run the closure without depending on anything it reads.
+    pub fn untrack<R>(&mut self, closure: impl FnOnce(&mut Self) -> R) -> R {
+        let untracked = self.untracked;
+        self.untracked = true;
+        let result = closure(self);
+        self.untracked = untracked;
+        result
+    }
+
+    /// Get at the world, e.g. for an effect to do its thing. Reads from it are not tracked.
+    pub fn world(&mut self) -> &mut World {
+        self.world
+    }
+
+    /// The sources read so far, in the order they were first read.
+    pub fn into_sources(self) -> Vec<Entity> {
+        self.read
+    }
+}
+
+/// ## Streams
+/// Most values a SignalStream keeps before it is polled. Past that, the oldest ones are dropped.
+pub const WATCH_QUEUE_CAPACITY: usize = 256;
+
+/// State shared between a SignalStream and the effect that feeds it.
+struct Watch<T: LazySignalsData> {
+    queue: VecDeque<LazySignalsResult<T>>,
+    waker: Option<Waker>,
+
+    /// Cleared when the effect is dropped (e.g. torn down along with the watched signal).
+    feeding: bool,
+}
+
+impl<T: LazySignalsData> Watch<T> {
+    /// Queue the value (dropping the oldest one if the queue is full) and wake the stream.
+    fn push(&mut self, value: LazySignalsResult<T>) {
+        if self.queue.len() >= WATCH_QUEUE_CAPACITY {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(value);
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Stream of each committed value of a signal or computed, for use in async code.
+/// It ends if the effect feeding it is torn down. If the watched entity is torn down, it yields
+/// a MissingSource error and then ends. A stream that is not polled keeps at most
+/// WATCH_QUEUE_CAPACITY values.
+pub struct SignalStream<T: LazySignalsData> {
+    watch: Arc<Mutex<Watch<T>>>,
+
+    /// Dropped along with the stream, which disconnects the receiver that destroys the effect.
+    _dropped: Sender<()>,
+}
+
+impl<T: LazySignalsData> Stream for SignalStream<T> {
+    type Item = LazySignalsResult<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut watch = self.watch.lock().unwrap();
+        if let Some(value) = watch.queue.pop_front() {
+            return Poll::Ready(Some(value));
+        }
+        if !watch.feeding {
+            return Poll::Ready(None);
+        }
+        watch.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// The effect end of a SignalStream. Dropping it ends the stream.
+pub struct WatchFeeder<T: LazySignalsData> {
+    watch: Arc<Mutex<Watch<T>>>,
+}
+
+impl<T: LazySignalsData> Drop for WatchFeeder<T> {
+    fn drop(&mut self) {
+        let mut watch = self.watch.lock().unwrap();
+        watch.feeding = false;
+        if let Some(waker) = watch.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// ## React-like getter/setter pair.
+/// The read half of a signal. It can be copied into closures and resources freely.
+pub struct Getter<T: LazySignalsData> {
+    signal: Signal<T>,
+}
+
+impl<T: LazySignalsData> Clone for Getter<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: LazySignalsData> Copy for Getter<T> {}
+
+impl<T: LazySignalsData> Readable<T> for Getter<T> {
+    fn entity(&self) -> Entity {
+        self.signal.entity
+    }
+}
+
+impl<T: LazySignalsData> Getter<T> {
+    /// Get the current value of the signal.
+    pub fn get(&self, world: &World) -> LazySignalsResult<T> {
+        LazySignals.read(self.signal, world)
+    }
+}
+
+/// The write half of a signal. It can be copied into closures and resources freely.
+pub struct Setter<T: LazySignalsData> {
+    signal: Signal<T>,
+}
+
+impl<T: LazySignalsData> Clone for Setter<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: LazySignalsData> Copy for Setter<T> {}
+
+impl<T: LazySignalsData> Setter<T> {
+    /// The underlying entity, for use with the lower-level commands API.
+    pub fn entity(&self) -> Entity {
+        self.signal.entity
+    }
+
+    /// Send the signal if the value is different from the current one.
+    pub fn set(&self, data: T, commands: &mut Commands) {
+        LazySignals.send(self.signal, data, commands);
+    }
+
+    /// Send the signal and notify subscribers even if the value did not change.
+    pub fn set_and_trigger(&self, data: T, commands: &mut Commands) {
+        LazySignals.send_and_trigger(self.signal, data, commands);
+    }
+}
+
+/// ## System params
+/// Reads signal values through a query, so ordinary systems don't need exclusive world access
+/// and can run in parallel with other systems.
+#[derive(SystemParam)]
+pub struct SignalReader<'w, 's, T: LazySignalsData> {
+    states: Query<'w, 's, &'static LazySignalsState<T>>,
+}
+
+impl<'w, 's, T: LazySignalsData> SignalReader<'w, 's, T> {
+    /// Get the current value, same as LazySignals.read.
+    pub fn read(&self, immutable: impl Readable<T>) -> LazySignalsResult<T> {
+        match self.states.get(immutable.entity()) {
+            Ok(state) => state.value(),
+            Err(QueryEntityError::NoSuchEntity(_)) => Some(Err(LazySignalsError::NoSignalError)),
+            Err(_) => Some(Err(LazySignalsError::ReadError(immutable.entity()))),
+        }
+    }
+
+    /// Get the current value, ignoring None and any errors in favor of the default.
+    pub fn read_or(&self, immutable: impl Readable<T>, default: T) -> T {
+        match self.read(immutable) {
+            Some(Ok(data)) => data,
+            _ => default,
+        }
+    }
+
+    /// Whether the current value (or reading it) is an error.
+    pub fn is_error(&self, immutable: impl Readable<T>) -> bool {
+        matches!(self.read(immutable), Some(Err(_)))
+    }
+}
+
 /// ## Main Signal primitive factory.
 /// Convenience functions for Signal creation and manipulation inspired by the TC39 proposal.
 pub struct LazySignals;
 impl LazySignals {
+    /// Run an async closure whenever a source changes and keep its progress in an AsyncValue<R>.
+    /// Each run cancels the one still in progress and results of superseded runs are thrown away.
+    /// LazySignalsAsyncValue<R> is only registered for the basic types, so register it for any
+    /// other R, same as any other custom LazySignalsState type.
+    pub fn async_computed<P: LazySignalsArgs, R: LazySignalsData, E: Display, F>(
+        &self,
+        closure: impl AsyncComputed<P, F>,
+        sources: Vec<Entity>,
+        commands: &mut Commands
+    ) -> Memo<AsyncValue<R>>
+        where F: Future<Output = Result<R, E>> + Send + 'static
+    {
+        let entity = commands.spawn_empty().id();
+        commands.create_async_computed::<P, R>(
+            entity,
+            make_async_computed_with(entity, closure),
+            sources
+        );
+        Memo { entity, data_type: PhantomData }
+    }
+
+    /// Stop a running task. Its commands never run. The task can still be triggered again.
+    /// This also takes the Memo of an async computed, which then stores Failed("cancelled").
+    pub fn cancel_task(&self, task: impl Into<Entity>, commands: &mut Commands) {
+        commands.cancel_task(task.into());
+    }
+
+    /// Write each value the signal commits into a reflected component field of the target, using
+    /// a path like Transform.translation.x (the component by its short or full type path).
+    pub fn bind_component_path<T: LazySignalsData>(
+        &self,
+        signal: impl Readable<T>,
+        target: Entity,
+        path: &str,
+        commands: &mut Commands
+    ) -> EffectHandle {
+        let entity = commands.spawn_empty().id();
+        commands.bind_component_path::<T>(entity, signal.entity(), target, path.to_string());
+        EffectHandle(entity)
+    }
+
     pub fn computed<P: LazySignalsArgs, R: LazySignalsData>(
         &self,
         propagator_closure: impl Computed<P, R>,
         sources: Vec<Entity>,
         commands: &mut Commands
-    ) -> Entity {
+    ) -> Memo<R> {
         let entity = commands.spawn_empty().id();
         commands.create_computed::<P, R>(entity, make_computed_with(propagator_closure), sources);
-        entity
+        Memo { entity, data_type: PhantomData }
+    }
+
+    /// Same as computed, but with a different way to decide whether the value changed, e.g. an
+    /// epsilon comparison for floats, or Always to notify the subscribers on every recompute.
+    pub fn computed_with_equality<P: LazySignalsArgs, R: LazySignalsData>(
+        &self,
+        propagator_closure: impl Computed<P, R>,
+        sources: Vec<Entity>,
+        equality: EqualityStrategy<R>,
+        commands: &mut Commands
+    ) -> Memo<R> {
+        let memo = self.computed(propagator_closure, sources, commands);
+        commands.set_equality(memo.entity, equality);
+        memo
+    }
+
+    /// Make a computed from a read-only Bevy system. The source values are passed in as In<P>, so
+    /// the system can also use Query, Res, etc. to compute its value. Besides being notified by
+    /// its sources, it recomputes whenever the components and resources it reads change.
+    /// Finding out checks every entity with each component it reads, once per frame.
+    pub fn computed_system<P: LazySignalsArgs, R: LazySignalsData, M, S>(
+        &self,
+        system: S,
+        sources: Vec<Entity>,
+        commands: &mut Commands
+    ) -> Memo<R>
+        where S: IntoSystem<P, LazySignalsResult<R>, M>, S::System: ReadOnlySystem
+    {
+        let entity = commands.spawn_empty().id();
+        commands.create_computed_system::<P, R>(
+            entity,
+            Box::new(IntoSystem::into_system(system)),
+            sources
+        );
+        Memo { entity, data_type: PhantomData }
     }
 
     pub fn effect<P: LazySignalsArgs>(
@@ -91,37 +845,218 @@ impl LazySignals {
         sources: Vec<Entity>,
         triggers: Vec<Entity>,
         commands: &mut Commands
-    ) -> Entity {
+    ) -> EffectHandle {
         let entity = commands.spawn_empty().id();
         commands.create_effect::<P>(entity, make_effect_with(effect_closure), sources, triggers);
-        entity
+        EffectHandle(entity)
+    }
+
+    /// Run a Bevy system as an effect. The source values are passed in as In<P>, so the system can
+    /// use Query, Res, EventWriter, etc. for its side-effects.
+    /// (LazySignalsResource is not available while effects run)
+    pub fn effect_system<P: LazySignalsArgs, M>(
+        &self,
+        system: impl IntoSystem<P, (), M>,
+        sources: Vec<Entity>,
+        triggers: Vec<Entity>,
+        commands: &mut Commands
+    ) -> EffectHandle {
+        let entity = commands.spawn_empty().id();
+        commands.create_effect_system::<P>(
+            entity,
+            Box::new(IntoSystem::into_system(system)),
+            sources,
+            triggers
+        );
+        EffectHandle(entity)
+    }
+
+    /// Send an event every time the source commits a new value (errors are skipped).
+    pub fn emit_events<T: LazySignalsData, E: Event>(
+        &self,
+        source: impl Readable<T>,
+        map_fn: impl Send + Sync + 'static + Fn(T) -> E,
+        commands: &mut Commands
+    ) -> EffectHandle {
+        self.effect::<(Option<T>,)>(
+            move |args, world| {
+                if let Some(data) = args.0 {
+                    world.send_event(map_fn(data));
+                }
+            },
+            vec![source.entity()],
+            Vec::<Entity>::new(),
+            commands
+        )
+    }
+
+    /// Tear down a signal, computed, effect, or task. The policy decides what happens downstream.
+    pub fn destroy(
+        &self,
+        entity: impl Into<Entity>,
+        policy: TeardownPolicy,
+        commands: &mut Commands
+    ) {
+        commands.destroy_signal(entity.into(), policy);
+    }
+
+    /// Make a state that mirrors a reflected component field of the target, using a path like
+    /// Transform.translation.x. It is sent whenever the component changes.
+    pub fn mirror_component_path<T: LazySignalsData>(
+        &self,
+        target: Entity,
+        path: &str,
+        commands: &mut Commands
+    ) -> Signal<T> {
+        let entity = commands.spawn_empty().id();
+        commands.mirror_component_path::<T>(entity, target, path.to_string());
+        Signal { entity, data_type: PhantomData }
+    }
+
+    /// Make a computed that extracts a field of a reflected struct signal by path (e.g.
+    /// stats.health). Since the memo only changes when that field does, effects downstream of it
+    /// don't run when some other field of the struct changes.
+    /// The memo is a LazySignalsState<F>, which is only registered for the basic types (e.g. not
+    /// for f32), so register it for any other F, same as any other custom LazySignalsState type.
+    pub fn project<T: LazySignalsData, F: LazySignalsData>(
+        &self,
+        source: impl Readable<T>,
+        path: &str,
+        commands: &mut Commands
+    ) -> Memo<F> {
+        let path = path.to_string();
+        self.computed::<(Option<T>,), F>(
+            move |args| {
+                let data = args.0?;
+                match data.reflect_path(path.as_str()).ok().and_then(F::from_reflect) {
+                    Some(field) => Some(Ok(field)),
+                    None => Some(Err(LazySignalsError::InvalidPath(path.clone()))),
+                }
+            },
+            vec![source.entity()],
+            commands
+        )
     }
 
     pub fn read<R: LazySignalsData>(
         &self,
-        immutable: Entity,
+        immutable: impl Readable<R>,
         world: &World
     ) -> LazySignalsResult<R> {
         self.value(immutable, world)
     }
 
-    pub fn send<T: LazySignalsData>(&self, signal: Entity, data: T, commands: &mut Commands) {
-        commands.send_signal::<T>(signal, data);
+    /// Read the value, ignoring None and any errors in favor of the default.
+    pub fn read_or<R: LazySignalsData>(
+        &self,
+        immutable: impl Readable<R>,
+        default: R,
+        world: &World
+    ) -> R {
+        match self.value(immutable, world) {
+            Some(Ok(data)) => data,
+            _ => default,
+        }
+    }
+
+    /// Point a computed or effect at different sources (and triggers, for an effect) without
+    /// tearing it down, e.g. to retarget a memo at another item. The new sources must still line
+    /// up with its args tuple and must not form a cycle, or else an error is recorded and it keeps
+    /// its old ones. With recompute, it runs again right away with the new sources.
+    pub fn rewire(
+        &self,
+        entity: impl Into<Entity>,
+        sources: Vec<Entity>,
+        triggers: Vec<Entity>,
+        recompute: bool,
+        commands: &mut Commands
+    ) {
+        commands.set_sources(entity.into(), sources, triggers, recompute);
+    }
+
+    /// Get a cloneable SignalSender that can send values to the signal from any thread, e.g. for
+    /// networking or audio code that can't get at Commands. The values are sent in a batch when
+    /// the LazySignals systems run, and the receiving end goes away once every sender is dropped.
+    pub fn sender<T: LazySignalsData>(
+        &self,
+        signal: Signal<T>,
+        commands: &mut Commands
+    ) -> SignalSender<T> {
+        let (sender, receiver) = channel::<T>();
+        commands.spawn(SignalReceiver {
+            function: make_receiver_with(signal.entity, receiver),
+            target: signal.entity,
+        });
+        SignalSender::new(sender)
+    }
+
+    pub fn send<T: LazySignalsData>(&self, signal: Signal<T>, data: T, commands: &mut Commands) {
+        commands.send_signal::<T>(signal.entity, data);
     }
 
     pub fn send_and_trigger<T: LazySignalsData>(
         &self,
-        signal: Entity,
+        signal: Signal<T>,
         data: T,
         commands: &mut Commands
     ) {
-        commands.trigger_signal::<T>(signal, data);
+        commands.trigger_signal::<T>(signal.entity, data);
+    }
+
+    /// Make a state and return a React-like getter/setter pair for it.
+    pub fn signal<T: LazySignalsData>(
+        &self,
+        data: T,
+        commands: &mut Commands
+    ) -> (Getter<T>, Setter<T>) {
+        let signal = self.state(data, commands);
+        (Getter { signal }, Setter { signal })
     }
 
-    pub fn state<T: LazySignalsData>(&self, data: T, commands: &mut Commands) -> Entity {
-        let state = commands.spawn_empty().id();
-        commands.create_state::<T>(state, data);
-        state
+    pub fn state<T: LazySignalsData>(&self, data: T, commands: &mut Commands) -> Signal<T> {
+        let entity = commands.spawn_empty().id();
+        commands.create_state::<T>(entity, data);
+        Signal { entity, data_type: PhantomData }
+    }
+
+    /// Same as state, but with a different way to decide whether a sent value is a change, e.g.
+    /// an epsilon comparison for floats, or Always to notify the subscribers on every send.
+    pub fn state_with_equality<T: LazySignalsData>(
+        &self,
+        data: T,
+        equality: EqualityStrategy<T>,
+        commands: &mut Commands
+    ) -> Signal<T> {
+        let signal = self.state(data, commands);
+        commands.set_equality(signal.entity, equality);
+        signal
+    }
+
+    /// Run an async task that can push intermediate values into the target signal while it runs.
+    /// Otherwise this works just like task_with.
+    pub fn streaming_task<P: LazySignalsArgs, T: LazySignalsData>(
+        &self,
+        task_closure: impl StreamingTask<P, T>,
+        target: Signal<T>,
+        sources: Vec<Entity>,
+        triggers: Vec<Entity>,
+        options: TaskOptions,
+        commands: &mut Commands
+    ) -> TaskHandle {
+        let entity = commands.spawn_empty().id();
+        let (sender, receiver) = channel::<T>();
+        commands.create_task::<P>(
+            entity,
+            make_streaming_task_with(task_closure, SignalSender::new(sender)),
+            sources,
+            triggers
+        );
+        commands.set_task_options(entity, options);
+        commands.entity(entity).insert(SignalReceiver {
+            function: make_receiver_with(target.entity, receiver),
+            target: target.entity,
+        });
+        TaskHandle(entity)
     }
 
     pub fn task<P: LazySignalsArgs>(
@@ -130,27 +1065,121 @@ impl LazySignals {
         sources: Vec<Entity>,
         triggers: Vec<Entity>,
         commands: &mut Commands
-    ) -> Entity {
+    ) -> TaskHandle {
         let entity = commands.spawn_empty().id();
         commands.create_task::<P>(entity, make_task_with(task_closure), sources, triggers);
-        entity
+        TaskHandle(entity)
+    }
+
+    /// Same as task, but the options decide what happens if it is triggered again while still
+    /// running. With a timeout, any run that takes longer is dropped and a TaskTimedOut error is
+    /// recorded.
+    pub fn task_with<P: LazySignalsArgs>(
+        &self,
+        task_closure: impl AsyncTask<P>,
+        sources: Vec<Entity>,
+        triggers: Vec<Entity>,
+        options: TaskOptions,
+        commands: &mut Commands
+    ) -> TaskHandle {
+        let task = self.task(task_closure, sources, triggers, commands);
+        commands.set_task_options(task.0, options);
+        task
     }
 
-    pub fn trigger(&self, signal: Entity, commands: &mut Commands) {
-        commands.trigger_signal::<()>(signal, ());
+    /// Make a computed whose sources are whatever its closure reads with TrackingContext::get,
+    /// instead of a fixed list of sources lined up with an args tuple. It computes once right
+    /// away to find out what it reads, then again whenever any of that changes.
+    pub fn tracked_computed<R: LazySignalsData>(
+        &self,
+        closure: impl TrackedComputed<R>,
+        commands: &mut Commands
+    ) -> Memo<R> {
+        let entity = commands.spawn_empty().id();
+        let tracked = Arc::new(Mutex::new(Vec::<Entity>::new()));
+        commands.create_tracked_computed::<R>(
+            entity,
+            make_tracked_computed_with(closure, tracked.clone()),
+            tracked
+        );
+        Memo { entity, data_type: PhantomData }
+    }
+
+    /// Make an effect whose sources are whatever its closure reads with TrackingContext::get.
+    /// It runs once right away, then whenever any of that changes or a trigger is sent.
+    pub fn tracked_effect(
+        &self,
+        closure: impl TrackedEffect,
+        triggers: Vec<Entity>,
+        commands: &mut Commands
+    ) -> EffectHandle {
+        let entity = commands.spawn_empty().id();
+        let tracked = Arc::new(Mutex::new(Vec::<Entity>::new()));
+        commands.create_tracked_effect(
+            entity,
+            make_tracked_effect_with(closure, tracked.clone()),
+            tracked,
+            triggers
+        );
+        EffectHandle(entity)
+    }
+
+    pub fn trigger(&self, signal: Signal<()>, commands: &mut Commands) {
+        commands.trigger_signal::<()>(signal.entity, ());
     }
 
     pub fn value<R: LazySignalsData>(
         &self,
-        immutable: Entity,
+        immutable: impl Readable<R>,
         world: &World
     ) -> LazySignalsResult<R> {
-        let entity = world.entity(immutable);
+        let immutable = immutable.entity();
+        let Some(entity) = world.get_entity(immutable) else {
+            return Some(Err(LazySignalsError::NoSignalError));
+        };
         match entity.get::<LazySignalsState<R>>() {
             Some(observable) => observable.value(),
 
-            // TODO maybe add some kind of config option to ignore errors and return a default
+            // use read_or (here or on SignalReader) to ignore errors and return a default instead
             None => Some(Err(LazySignalsError::ReadError(immutable))),
         }
     }
+
+    /// Get a Stream that yields each value the signal or computed commits from now on, so async
+    /// code can await changes instead of polling read. Values are pushed by an effect, so they
+    /// show up once apply_deferred_effects runs. The effect goes away after the stream is dropped,
+    /// and the stream ends (after a MissingSource error) if the watched entity is destroyed.
+    pub fn watch<T: LazySignalsData>(
+        &self,
+        immutable: impl Readable<T>,
+        commands: &mut Commands
+    ) -> SignalStream<T> {
+        let watch = Arc::new(
+            Mutex::new(Watch {
+                queue: VecDeque::new(),
+                waker: None,
+                feeding: true,
+            })
+        );
+        let feeder = WatchFeeder { watch: watch.clone() };
+        let entity = commands.spawn_empty().id();
+
+        // the value is read by the effect itself, so the watched entity is just a trigger
+        commands.create_effect::<()>(
+            entity,
+            make_watcher_with(immutable.entity(), entity, feeder),
+            Vec::<Entity>::new(),
+            vec![immutable.entity()]
+        );
+
+        // nobody is listening once the stream is dropped, so the effect can go away right then
+        // instead of the next time the watched entity changes
+        let (guard, dropped) = channel::<()>();
+        commands.entity(entity).insert(WatchGuard {
+            watched: immutable.entity(),
+            dropped: Mutex::new(dropped),
+            close: make_watch_closer_with(watch.clone()),
+        });
+        SignalStream { watch, _dropped: guard }
+    }
 }
@@ -8,7 +8,7 @@ use bevy::{
         world::EntityWorldMut,
     },
     prelude::*,
-    reflect::{ DynamicTuple, ReflectFromPtr, TypeRegistry },
+    reflect::{ DynamicTuple, ReflectFromPtr, TypeInfo, TypeRegistry },
 };
 
 use crate::{
@@ -17,21 +17,22 @@ use crate::{
 };
 
 /// Given mutable reference to a LazySignalsState component instance, make a LazySignalsObservable.
+/// Returns None if the concrete LazySignalsState<T> type was never registered for reflection.
 pub fn ph_nglui_mglw_nafh_cthulhu_r_lyeh_wgah_nagl_fhtagn<'a>(
     mut_untyped: &'a mut MutUntyped,
     type_id: &TypeId,
     type_registry: &RwLockReadGuard<TypeRegistry>
-) -> &'a mut dyn LazySignalsObservable {
+) -> Option<&'a mut dyn LazySignalsObservable> {
     // convert into a pointer
     let ptr_mut = mut_untyped.as_mut();
 
     // the reflect_data is used to build a strategy to dereference a pointer to the component
 
     // the TypeId refers to the LazySignalsState<T> component with concrete T
-    let reflect_data = type_registry.get(*type_id).unwrap();
+    let reflect_data = type_registry.get(*type_id)?;
 
     // since we're reflecting from a pointer, we're gonna need this
-    let reflect_from_ptr = reflect_data.data::<ReflectFromPtr>().unwrap().clone();
+    let reflect_from_ptr = reflect_data.data::<ReflectFromPtr>()?.clone();
 
     // I think we're sorta getting a proxy to the vtable for the concrete type and then schlepping
     // it into the reflected proxy for the pointer to the concrete component (value)
@@ -43,12 +44,12 @@ pub fn ph_nglui_mglw_nafh_cthulhu_r_lyeh_wgah_nagl_fhtagn<'a>(
     let value = unsafe { reflect_from_ptr.as_reflect_mut(ptr_mut) };
 
     // the sun grew dark and cold
-    let reflect_observable = type_registry
-        .get_type_data::<ReflectLazySignalsObservable>(value.type_id())
-        .unwrap();
+    let reflect_observable = type_registry.get_type_data::<ReflectLazySignalsObservable>(
+        value.type_id()
+    )?;
 
     // the seas boiled
-    reflect_observable.get_mut(value).unwrap()
+    reflect_observable.get_mut(value)
 }
 
 /// Make a LazySignalsObservable out of EntityWorldMut, passing optional args and target Entity.
@@ -61,19 +62,59 @@ pub fn run_as_observable(
     type_id: &TypeId,
     type_registry: &RwLockReadGuard<TypeRegistry>,
     mut closure: Box<dyn ObservableFn>
-) -> MaybeFlaggedEntities {
+) -> Result<MaybeFlaggedEntities, LazySignalsError> {
+    let id = entity.id();
+
     // get the source LazySignalsState component as an ECS change detection handle
-    let mut mut_untyped = entity.get_mut_by_id(*component_id).unwrap();
+    let mut mut_untyped = entity
+        .get_mut_by_id(*component_id)
+        .ok_or(LazySignalsError::ReflectError(id))?;
 
     // ...and convert that into a trait object
     let observable = ph_nglui_mglw_nafh_cthulhu_r_lyeh_wgah_nagl_fhtagn(
         &mut mut_untyped,
         type_id,
         type_registry
-    );
+    ).ok_or(LazySignalsError::ReflectError(id))?;
 
     // run the supplied fn
-    closure(Box::new(observable), args, target)
+    Ok(closure(Box::new(observable), args, target))
+}
+
+/// Get the current error (if any) stored in the LazySignalsState of the entity.
+pub fn get_error(
+    entity: &mut EntityWorldMut,
+    component_id: &ComponentId,
+    type_id: &TypeId,
+    type_registry: &RwLockReadGuard<TypeRegistry>
+) -> Result<Option<LazySignalsError>, LazySignalsError> {
+    let id = entity.id();
+    let mut mut_untyped = entity
+        .get_mut_by_id(*component_id)
+        .ok_or(LazySignalsError::ReflectError(id))?;
+    let observable = ph_nglui_mglw_nafh_cthulhu_r_lyeh_wgah_nagl_fhtagn(
+        &mut mut_untyped,
+        type_id,
+        type_registry
+    ).ok_or(LazySignalsError::ReflectError(id))?;
+    Ok(observable.error())
+}
+
+/// Get the ComponentId and TypeId of the LazySignalsState of a source, if it has one.
+fn observable_ids(source: &Entity, world: &World) -> Option<(ComponentId, TypeId)> {
+    // get a readonly reference to the source entity
+    let source = world.get_entity(*source)?;
+    trace!("-got source EntityRef");
+
+    // get the source Immutable component
+    let immutable_state = source.get::<ImmutableState>()?;
+    trace!("-got ImmutableState");
+
+    // ...as a SignalsObservable
+    let component_id = immutable_state.component_id;
+    let type_id = world.components().get_info(component_id)?.type_id()?;
+    trace!("-got TypeId");
+    Some((component_id, type_id))
 }
 
 /// Convenience fn to subscribe an entity to a source.
@@ -82,47 +123,189 @@ pub fn subscribe(
     source: &Entity,
     type_registry: &RwLockReadGuard<TypeRegistry>,
     world: &mut World
-) {
-    // get the TypeId of each source (Signal or Computed) component
-    let mut component_id: Option<ComponentId> = None;
-    let mut type_id: Option<TypeId> = None;
-
+) -> Result<(), LazySignalsError> {
     trace!("Subscribing {:#?} to {:?}", entity, source);
 
-    // get a readonly reference to the source entity
-    if let Some(source) = world.get_entity(*source) {
-        trace!("-got source EntityRef");
-        // get the source Immutable component
-        if let Some(immutable_state) = source.get::<ImmutableState>() {
-            trace!("-got ImmutableState");
-            // ...as a SignalsObservable
-            component_id = Some(immutable_state.component_id);
-            if let Some(info) = world.components().get_info(component_id.unwrap()) {
-                trace!("-got TypeId");
-                type_id = info.type_id();
-            }
+    // get the TypeId of each source (Signal or Computed) component
+    if let Some((component_id, type_id)) = observable_ids(source, world) {
+        // we have a component and a type, now do mut stuff
+        if let Some(mut source) = world.get_entity_mut(*source) {
+            run_as_observable(
+                &mut source,
+                None,
+                Some(entity),
+                &component_id,
+                &type_id,
+                type_registry,
+                Box::new(|observable, _args, target| {
+                    observable.subscribe(*target.unwrap());
+                    observable.merge_subscribers();
+                    None
+                })
+            )?;
         }
     }
+    Ok(())
+}
 
-    // we have a component and a type, now do mut stuff
-    if component_id.is_some() && type_id.is_some() {
-        if let Some(mut source) = world.get_entity_mut(*source) {
-            let component_id = &component_id.unwrap();
-            let type_id = type_id.unwrap();
+/// Convenience fn to unsubscribe an entity from a source (e.g. when tearing it down).
+pub fn unsubscribe(
+    entity: &Entity,
+    source: &Entity,
+    type_registry: &RwLockReadGuard<TypeRegistry>,
+    world: &mut World
+) -> Result<(), LazySignalsError> {
+    trace!("Unsubscribing {:#?} from {:?}", entity, source);
 
+    if let Some((component_id, type_id)) = observable_ids(source, world) {
+        if let Some(mut source) = world.get_entity_mut(*source) {
             run_as_observable(
                 &mut source,
                 None,
                 Some(entity),
-                component_id,
+                &component_id,
                 &type_id,
                 type_registry,
                 Box::new(|observable, _args, target| {
-                    observable.subscribe(*target.unwrap());
-                    observable.merge_subscribers();
+                    observable.unsubscribe(*target.unwrap());
                     None
                 })
-            );
+            )?;
         }
     }
+    Ok(())
+}
+
+/// Swap the subscriptions of a tracked computed or effect from the sources it read last time to
+/// the ones it read this time. Sources that were read both times are left alone.
+pub fn retrack(
+    entity: &Entity,
+    old: &[Entity],
+    new: &[Entity],
+    type_registry: &RwLockReadGuard<TypeRegistry>,
+    world: &mut World
+) -> Result<(), LazySignalsError> {
+    for source in old.iter().filter(|source| !new.contains(source)) {
+        unsubscribe(entity, source, type_registry, world)?;
+    }
+    for source in new.iter() {
+        subscribe(entity, source, type_registry, world)?;
+    }
+    Ok(())
+}
+
+/// Walk upstream from the given sources through any computeds, looking for the entity itself.
+/// Returns the offending path (starting and ending with the entity) if there is a cycle.
+pub fn find_cycle(entity: &Entity, sources: &[Entity], world: &World) -> Option<Vec<Entity>> {
+    let mut visited = empty_set();
+
+    // depth-first, keeping the whole path to each node so it can be reported
+    let mut stack = sources
+        .iter()
+        .map(|source| vec![*entity, *source])
+        .collect::<Vec<Vec<Entity>>>();
+
+    while let Some(path) = stack.pop() {
+        let node = *path.last().unwrap();
+        if node == *entity {
+            return Some(path);
+        }
+        if visited.contains(node) {
+            continue;
+        }
+        visited.insert(node, ());
+
+        // only computeds have sources of their own (signals are leaves, effects are never sources)
+        // except an async computed, which is a state updated by an effect on the same entity
+        let Some(node) = world.get_entity(node) else {
+            continue;
+        };
+        let mut upstream = Vec::<Entity>::new();
+        if let Some(computed) = node.get::<ComputedImmutable>() {
+            upstream.extend(computed.sources.iter());
+        }
+        if let Some(effect) = node.get::<LazyEffect>() {
+            if node.contains::<ImmutableState>() {
+                upstream.extend(effect.sources.iter());
+                upstream.extend(effect.triggers.iter());
+            }
+        }
+        for source in upstream {
+            let mut next = path.clone();
+            next.push(source);
+            stack.push(next);
+        }
+    }
+    None
+}
+
+/// Compare the field types of the args tuple with the type of each source, in order.
+pub fn check_args(
+    sources: &[Entity],
+    args_info: &TypeInfo,
+    world: &World
+) -> Result<(), LazySignalsError> {
+    let TypeInfo::Tuple(info) = args_info else {
+        return Ok(());
+    };
+
+    for index in 0..info.field_len().max(sources.len()) {
+        let field = info.field_at(index);
+        let source = sources
+            .get(index)
+            .and_then(|source| world.get_entity(*source))
+            .and_then(|source| source.get::<ImmutableState>());
+        if field.map(|f| f.type_id()) != source.map(|s| s.arg_type) {
+            return Err(LazySignalsError::ArgsTypeMismatch {
+                index,
+                expected: field.map_or("nothing", |f| f.type_path()).to_string(),
+                found: source.map_or("nothing", |s| s.arg_type_path).to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Convenience fn to send an error to an entity as if it were a signal.
+pub fn send_error(
+    entity: &Entity,
+    error: LazySignalsError,
+    type_registry: &RwLockReadGuard<TypeRegistry>,
+    world: &mut World
+) -> Result<(), LazySignalsError> {
+    if let Some((component_id, type_id)) = observable_ids(entity, world) {
+        if let Some(mut handle) = world.get_entity_mut(*entity) {
+            run_as_observable(
+                &mut handle,
+                None,
+                None,
+                &component_id,
+                &type_id,
+                type_registry,
+                Box::new(move |observable, _args, _target| {
+                    observable.merge_error(error.clone());
+                    None
+                })
+            )?;
+            handle.insert(SendSignal);
+        }
+    }
+    Ok(())
+}
+
+/// Split a path like Transform.translation.x into the ReflectComponent and TypeId of the
+/// component (by short or full type path) and the path of the field within it.
+pub fn resolve_component_path(
+    path: &str,
+    world: &World
+) -> Result<(ReflectComponent, TypeId, String), LazySignalsError> {
+    let error = || LazySignalsError::InvalidPath(path.to_string());
+    let (component, field) = path.split_once('.').ok_or_else(error)?;
+    let type_registry = world.get_resource::<AppTypeRegistry>().ok_or_else(error)?.read();
+    let registration = type_registry
+        .get_with_short_type_path(component)
+        .or_else(|| type_registry.get_with_type_path(component))
+        .ok_or_else(error)?;
+    let reflect_component = registration.data::<ReflectComponent>().ok_or_else(error)?.clone();
+    Ok((reflect_component, registration.type_id(), field.to_string()))
 }
@@ -0,0 +1,110 @@
+use std::sync::{ Arc, Mutex };
+
+use bevy::{ ecs::component::Tick, prelude::* };
+
+use crate::{
+    api::{ Signal, SignalReader },
+    commands::LazySignalsCommandsExt,
+    framework::*,
+    LazySignalsResource,
+    LazySignalsSystemSet,
+};
+
+/// What the two systems of a resource binding tell each other so neither one echoes the other.
+#[derive(Default)]
+struct Synced {
+    /// The change tick of the last write to the resource by the signal side.
+    written: Option<Tick>,
+
+    /// Whether the resource side sent the signal this frame.
+    sent: bool,
+}
+
+/// Convenience extension to wire the rest of a Bevy app to signals without writing glue systems.
+/// These systems run in PreUpdate, before LazySignalsSystemSet.
+pub trait LazySignalsAppExt {
+    /// Send the signal whenever an event of type E is read, using the mapped value of the last
+    /// event read in each frame (the others would just overwrite it).
+    fn signal_from_event<E: Event, T: LazySignalsData>(
+        &mut self,
+        signal: Signal<T>,
+        map_fn: impl Send + Sync + 'static + Fn(&E) -> T
+    ) -> &mut Self;
+
+    /// Keep a field of a resource and a signal in sync. The signal is sent when change detection
+    /// fires for the resource, and the resource is written when the signal commits a change (as
+    /// decided by its EqualityStrategy). Changes that came from the other side are not sent back.
+    /// At first the resource wins.
+    fn bind_resource<R: Resource, T: LazySignalsData>(
+        &mut self,
+        signal: Signal<T>,
+        getter: impl Send + Sync + 'static + Fn(&R) -> T,
+        setter: impl Send + Sync + 'static + Fn(&mut R, T)
+    ) -> &mut Self;
+}
+
+impl LazySignalsAppExt for App {
+    fn signal_from_event<E: Event, T: LazySignalsData>(
+        &mut self,
+        signal: Signal<T>,
+        map_fn: impl Send + Sync + 'static + Fn(&E) -> T
+    ) -> &mut Self {
+        self.add_systems(
+            PreUpdate,
+            (move |mut events: EventReader<E>, mut commands: Commands| {
+                if let Some(data) = events.read().map(&map_fn).last() {
+                    commands.send_signal(signal.into(), data);
+                }
+            }).before(LazySignalsSystemSet)
+        )
+    }
+
+    fn bind_resource<R: Resource, T: LazySignalsData>(
+        &mut self,
+        signal: Signal<T>,
+        getter: impl Send + Sync + 'static + Fn(&R) -> T,
+        setter: impl Send + Sync + 'static + Fn(&mut R, T)
+    ) -> &mut Self {
+        // neither side echoes the other, and this goes by change ticks and the changed set rather
+        // than by value, so values that are not equal to themselves (e.g. NaN) don't bounce back
+        let synced = Arc::new(Mutex::new(Synced::default()));
+        let from_resource = synced.clone();
+        let to_resource = synced;
+
+        self.add_systems(
+            PreUpdate,
+            (
+                (move |resource: Option<Res<R>>, mut commands: Commands| {
+                    let Some(resource) = resource.filter(|resource| resource.is_changed()) else {
+                        return;
+                    };
+                    let mut synced = from_resource.lock().unwrap();
+                    if synced.written == Some(resource.last_changed()) {
+                        return;
+                    }
+                    synced.sent = true;
+                    commands.send_signal(signal.into(), getter(&resource));
+                }).before(LazySignalsSystemSet),
+                (move |
+                    resource: Option<ResMut<R>>,
+                    signals: SignalReader<T>,
+                    lazy_signals: Res<LazySignalsResource>
+                | {
+                    let Some(mut resource) = resource else {
+                        return;
+                    };
+                    let mut synced = to_resource.lock().unwrap();
+                    let sent = std::mem::take(&mut synced.sent);
+                    if sent || !lazy_signals.changed.contains(signal.into()) {
+                        return;
+                    }
+                    let Some(Ok(data)) = signals.read(signal) else {
+                        return;
+                    };
+                    setter(&mut resource, data);
+                    synced.written = Some(resource.last_changed());
+                }).after(LazySignalsSystemSet),
+            )
+        )
+    }
+}
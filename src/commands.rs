@@ -1,11 +1,52 @@
-use std::{ marker::PhantomData, sync::Mutex };
+use std::{ marker::PhantomData, sync::{ atomic::{ AtomicU64, Ordering }, Arc, Mutex } };
 
-use bevy::{ ecs::world::Command, prelude::* };
+use bevy::{ ecs::{ system::BoxedSystem, world::Command }, prelude::* };
 
-use crate::{ bundles::*, framework::*, lazy_immutable::{ LazySignalsState, LazySignalsImmutable } };
+use crate::{
+    api::{
+        make_change_detector_with,
+        make_computed_system_with,
+        make_effect_system_with,
+        make_path_mirror_with,
+        make_path_setter_with,
+    },
+    arcane_wizardry::{
+        check_args,
+        find_cycle,
+        resolve_component_path,
+        send_error,
+        subscribe,
+        unsubscribe,
+    },
+    bundles::*,
+    framework::*,
+    lazy_immutable::{ LazySignalsState, LazySignalsImmutable },
+    clear_errors,
+    record_error,
+};
 
 /// Convenience extension to use each Command directly from Commands instance.
 pub trait LazySignalsCommandsExt {
+    /// Command to create an async computed (state plus effect that runs a future) from the entity.
+    fn create_async_computed<P: LazySignalsArgs, R: LazySignalsData>(
+        &mut self,
+        computed: Entity,
+        function: Mutex<Box<dyn EffectWrapper>>,
+        sources: Vec<Entity>
+    );
+
+    /// Command to create an effect that writes the signal into a component field path.
+    fn bind_component_path<T: LazySignalsData>(
+        &mut self,
+        effect: Entity,
+        signal: Entity,
+        target: Entity,
+        path: String
+    );
+
+    /// Command to drop the running Task of the given task entity, if any.
+    fn cancel_task(&mut self, task: Entity);
+
     /// Command to create a computed memo from the given entity.
     fn create_computed<P: LazySignalsArgs, R: LazySignalsData>(
         &mut self,
@@ -14,6 +55,14 @@ pub trait LazySignalsCommandsExt {
         sources: Vec<Entity>
     );
 
+    /// Command to create a computed memo that runs a read-only Bevy system from the given entity.
+    fn create_computed_system<P: LazySignalsArgs, R: LazySignalsData>(
+        &mut self,
+        computed: Entity,
+        system: BoxedSystem<P, LazySignalsResult<R>>,
+        sources: Vec<Entity>
+    );
+
     /// Command to create a short-lived effect from the given entity.
     fn create_effect<P: LazySignalsArgs>(
         &mut self,
@@ -23,6 +72,18 @@ pub trait LazySignalsCommandsExt {
         triggers: Vec<Entity>
     );
 
+    /// Command to create an effect that runs a Bevy system from the given entity.
+    fn create_effect_system<P: LazySignalsArgs>(
+        &mut self,
+        effect: Entity,
+        system: BoxedSystem<P>,
+        sources: Vec<Entity>,
+        triggers: Vec<Entity>
+    );
+
+    /// Command to tear down a signal, computed, effect, or task (see TeardownPolicy).
+    fn destroy_signal(&mut self, signal: Entity, policy: TeardownPolicy);
+
     /// Command to create a state (LazyImmutable with no Effect or Propagator) from the given entity.
     fn create_state<T: LazySignalsData>(&mut self, state: Entity, data: T);
 
@@ -35,14 +96,89 @@ pub trait LazySignalsCommandsExt {
         triggers: Vec<Entity>
     );
 
+    /// Command to create a computed memo from the given entity that tracks its own sources.
+    fn create_tracked_computed<R: LazySignalsData>(
+        &mut self,
+        computed: Entity,
+        function: Mutex<Box<dyn ComputedContext>>,
+        tracked: Arc<Mutex<Vec<Entity>>>
+    );
+
+    /// Command to create a short-lived effect from the given entity that tracks its own sources.
+    fn create_tracked_effect(
+        &mut self,
+        effect: Entity,
+        function: Mutex<Box<dyn EffectWrapper>>,
+        tracked: Arc<Mutex<Vec<Entity>>>,
+        triggers: Vec<Entity>
+    );
+
+    /// Command to create a state that mirrors a component field path from the given entity.
+    fn mirror_component_path<T: LazySignalsData>(
+        &mut self,
+        state: Entity,
+        target: Entity,
+        path: String
+    );
+
     // Command to send a signal if the data value is different from the current value.
     fn send_signal<T: LazySignalsData>(&mut self, signal: Entity, data: T);
 
+    /// Command to change how a state or computed decides whether its value changed.
+    fn set_equality<T: LazySignalsData>(&mut self, state: Entity, equality: EqualityStrategy<T>);
+
+    /// Command to change the retrigger policy and timeout of a task.
+    fn set_task_options(&mut self, task: Entity, options: TaskOptions);
+
+    /// Command to replace the sources (and triggers) of a computed or effect.
+    fn set_sources(
+        &mut self,
+        entity: Entity,
+        sources: Vec<Entity>,
+        triggers: Vec<Entity>,
+        recompute: bool
+    );
+
     // Command to send a signal even if the data value is unchanged.
     fn trigger_signal<T: LazySignalsData>(&mut self, signal: Entity, data: T);
 }
 
 impl<'w, 's> LazySignalsCommandsExt for Commands<'w, 's> {
+    fn create_async_computed<P: LazySignalsArgs, R: LazySignalsData>(
+        &mut self,
+        computed: Entity,
+        function: Mutex<Box<dyn EffectWrapper>>,
+        sources: Vec<Entity>
+    ) {
+        self.add(CreateAsyncComputedCommand::<P, R> {
+            computed,
+            function,
+            sources,
+            args_type: PhantomData,
+            result_type: PhantomData,
+        });
+    }
+
+    fn bind_component_path<T: LazySignalsData>(
+        &mut self,
+        effect: Entity,
+        signal: Entity,
+        target: Entity,
+        path: String
+    ) {
+        self.add(BindComponentPathCommand::<T> {
+            effect,
+            signal,
+            target,
+            path,
+            data_type: PhantomData,
+        });
+    }
+
+    fn cancel_task(&mut self, task: Entity) {
+        self.add(CancelTaskCommand { task });
+    }
+
     fn create_computed<P: LazySignalsArgs, R: LazySignalsData>(
         &mut self,
         computed: Entity,
@@ -58,6 +194,19 @@ impl<'w, 's> LazySignalsCommandsExt for Commands<'w, 's> {
         });
     }
 
+    fn create_computed_system<P: LazySignalsArgs, R: LazySignalsData>(
+        &mut self,
+        computed: Entity,
+        system: BoxedSystem<P, LazySignalsResult<R>>,
+        sources: Vec<Entity>
+    ) {
+        self.add(CreateComputedSystemCommand::<P, R> {
+            computed,
+            system,
+            sources,
+        });
+    }
+
     fn create_effect<P: LazySignalsArgs>(
         &mut self,
         effect: Entity,
@@ -74,6 +223,28 @@ impl<'w, 's> LazySignalsCommandsExt for Commands<'w, 's> {
         });
     }
 
+    fn create_effect_system<P: LazySignalsArgs>(
+        &mut self,
+        effect: Entity,
+        system: BoxedSystem<P>,
+        sources: Vec<Entity>,
+        triggers: Vec<Entity>
+    ) {
+        self.add(CreateEffectSystemCommand::<P> {
+            effect,
+            system,
+            sources,
+            triggers,
+        });
+    }
+
+    fn destroy_signal(&mut self, signal: Entity, policy: TeardownPolicy) {
+        self.add(DestroySignalCommand {
+            signal,
+            policy,
+        });
+    }
+
     fn create_state<T: LazySignalsData>(&mut self, state: Entity, data: T) {
         self.add(CreateStateCommand {
             state,
@@ -97,6 +268,49 @@ impl<'w, 's> LazySignalsCommandsExt for Commands<'w, 's> {
         });
     }
 
+    fn create_tracked_computed<R: LazySignalsData>(
+        &mut self,
+        computed: Entity,
+        function: Mutex<Box<dyn ComputedContext>>,
+        tracked: Arc<Mutex<Vec<Entity>>>
+    ) {
+        self.add(CreateTrackedComputedCommand::<R> {
+            computed,
+            function,
+            tracked,
+            result_type: PhantomData,
+        });
+    }
+
+    fn create_tracked_effect(
+        &mut self,
+        effect: Entity,
+        function: Mutex<Box<dyn EffectWrapper>>,
+        tracked: Arc<Mutex<Vec<Entity>>>,
+        triggers: Vec<Entity>
+    ) {
+        self.add(CreateTrackedEffectCommand {
+            effect,
+            function,
+            tracked,
+            triggers,
+        });
+    }
+
+    fn mirror_component_path<T: LazySignalsData>(
+        &mut self,
+        state: Entity,
+        target: Entity,
+        path: String
+    ) {
+        self.add(MirrorComponentPathCommand::<T> {
+            state,
+            target,
+            path,
+            data_type: PhantomData,
+        });
+    }
+
     fn send_signal<T: LazySignalsData>(&mut self, signal: Entity, data: T) {
         self.add(SendSignalCommand {
             signal,
@@ -104,6 +318,35 @@ impl<'w, 's> LazySignalsCommandsExt for Commands<'w, 's> {
         });
     }
 
+    fn set_equality<T: LazySignalsData>(&mut self, state: Entity, equality: EqualityStrategy<T>) {
+        self.add(SetEqualityCommand {
+            state,
+            equality,
+        });
+    }
+
+    fn set_task_options(&mut self, task: Entity, options: TaskOptions) {
+        self.add(SetTaskOptionsCommand {
+            task,
+            options,
+        });
+    }
+
+    fn set_sources(
+        &mut self,
+        entity: Entity,
+        sources: Vec<Entity>,
+        triggers: Vec<Entity>,
+        recompute: bool
+    ) {
+        self.add(SetSourcesCommand {
+            entity,
+            sources,
+            triggers,
+            recompute,
+        });
+    }
+
     fn trigger_signal<T: LazySignalsData>(&mut self, signal: Entity, data: T) {
         self.add(TriggerSignalCommand {
             signal,
@@ -112,6 +355,85 @@ impl<'w, 's> LazySignalsCommandsExt for Commands<'w, 's> {
     }
 }
 
+/// Command to create an effect that writes a signal into a component field from the given entity.
+pub struct BindComponentPathCommand<T: LazySignalsData> {
+    effect: Entity,
+    signal: Entity,
+    target: Entity,
+    path: String,
+    data_type: PhantomData<T>,
+}
+
+impl<T: LazySignalsData> Command for BindComponentPathCommand<T> {
+    fn apply(self, world: &mut World) {
+        // the type registry is not available while effects run, so look everything up now
+        match resolve_component_path(&self.path, world) {
+            Ok((component, _, field)) => {
+                world
+                    .get_entity_mut(self.effect)
+                    .unwrap()
+                    .insert(
+                        EffectBundle::from_function::<(Option<T>,)>(
+                            EffectContext::Short(
+                                make_path_setter_with::<T>(self.target, component, field, self.path)
+                            ),
+                            vec![self.signal],
+                            Vec::<Entity>::new()
+                        )
+                    );
+            }
+            Err(error) => {
+                error!("Could not bind {:?} to {}", self.signal, self.path);
+                record_error(world, self.effect, error);
+            }
+        }
+    }
+}
+
+/// Command to create a state that mirrors a component field from the given entity.
+pub struct MirrorComponentPathCommand<T: LazySignalsData> {
+    state: Entity,
+    target: Entity,
+    path: String,
+    data_type: PhantomData<T>,
+}
+
+impl<T: LazySignalsData> Command for MirrorComponentPathCommand<T> {
+    fn apply(self, world: &mut World) {
+        let component_id = world.init_component::<LazySignalsState<T>>();
+        match resolve_component_path(&self.path, world) {
+            Ok((component, component_type, field)) => {
+                // the value shows up the first time mirror_component_paths runs
+                world
+                    .get_entity_mut(self.state)
+                    .unwrap()
+                    .insert((
+                        StateBundle::<T>::from_result(None, component_id),
+                        ComponentPathMirror {
+                            function: make_path_mirror_with::<T>(
+                                self.state,
+                                self.target,
+                                component,
+                                component_type,
+                                field,
+                                self.path
+                            ),
+                        },
+                    ));
+            }
+            Err(error) => {
+                // keep the error as the value so readers can tell what went wrong
+                error!("Could not mirror {}", self.path);
+                world
+                    .get_entity_mut(self.state)
+                    .unwrap()
+                    .insert(StateBundle::<T>::from_result(Some(Err(error.clone())), component_id));
+                record_error(world, self.state, error);
+            }
+        }
+    }
+}
+
 /// Command to create a computed memo (Immutable plus Propagator) from the given entity.
 pub struct CreateComputedCommand<P: LazySignalsArgs, R: LazySignalsData> {
     computed: Entity,
@@ -134,6 +456,93 @@ impl<P: LazySignalsArgs, R: LazySignalsData> Command for CreateComputedCommand<P
     }
 }
 
+/// Command to create an async computed from the given entity. The entity holds both the
+/// LazySignalsState<AsyncValue<R>> and the effect that starts the future when a source changes.
+/// (like any other state type, LazySignalsState<AsyncValue<R>> must be registered for reflection)
+pub struct CreateAsyncComputedCommand<P: LazySignalsArgs, R: LazySignalsData> {
+    computed: Entity,
+    function: Mutex<Box<dyn EffectWrapper>>,
+    sources: Vec<Entity>,
+    args_type: PhantomData<P>,
+    result_type: PhantomData<R>,
+}
+
+impl<P: LazySignalsArgs, R: LazySignalsData> Command for CreateAsyncComputedCommand<P, R> {
+    fn apply(self, world: &mut World) {
+        let component_id = world.init_component::<LazySignalsState<AsyncValue<R>>>();
+        world
+            .get_entity_mut(self.computed)
+            .unwrap()
+            .insert((
+                StateBundle::<AsyncValue<R>>::from_value(AsyncValue::Idle, component_id),
+                EffectBundle::from_function::<P>(
+                    EffectContext::Short(self.function),
+                    self.sources,
+                    Vec::<Entity>::new()
+                ),
+                // a new run cancels the one still in progress
+                TaskRetriggerPolicy::CancelAndRestart,
+                AsyncComputedReset {
+                    function: Arc::new(move |world| {
+                        SendSignalCommand {
+                            signal: self.computed,
+                            data: AsyncValue::<R>::Failed("cancelled".to_string()),
+                        }.apply(world);
+                    }),
+                },
+            ));
+    }
+}
+
+/// Command to store the result of an async computed, unless a newer run started since.
+pub struct StoreAsyncValueCommand<R: LazySignalsData> {
+    pub(crate) computed: Entity,
+    pub(crate) generation: u64,
+    pub(crate) latest: Arc<AtomicU64>,
+    pub(crate) value: AsyncValue<R>,
+}
+
+impl<R: LazySignalsData> Command for StoreAsyncValueCommand<R> {
+    fn apply(self, world: &mut World) {
+        if self.latest.load(Ordering::SeqCst) != self.generation {
+            trace!("Dropping stale result of run {} for {:?}", self.generation, self.computed);
+            return;
+        }
+        SendSignalCommand {
+            signal: self.computed,
+            data: self.value,
+        }.apply(world);
+    }
+}
+
+/// Command to create a computed memo that runs a read-only Bevy system from the given entity.
+pub struct CreateComputedSystemCommand<P: LazySignalsArgs, R: LazySignalsData> {
+    computed: Entity,
+    system: BoxedSystem<P, LazySignalsResult<R>>,
+    sources: Vec<Entity>,
+}
+
+impl<P: LazySignalsArgs, R: LazySignalsData> Command for CreateComputedSystemCommand<P, R> {
+    fn apply(mut self, world: &mut World) {
+        // initialize now so the change detector knows what the system reads before it first runs
+        self.system.initialize(world);
+        let system = Arc::new(Mutex::new(self.system));
+
+        let component_id = world.init_component::<LazySignalsState<R>>();
+        world
+            .get_entity_mut(self.computed)
+            .unwrap()
+            .insert((
+                ComputedBundle::<R>::from_function::<P>(
+                    make_computed_system_with(system.clone()),
+                    self.sources,
+                    component_id
+                ),
+                ComputedSystem { changed: make_change_detector_with(system) },
+            ));
+    }
+}
+
 /// Command to create an effect (Propagator with no memo) from the given entity.
 pub struct CreateEffectCommand<P: LazySignalsArgs> {
     effect: Entity,
@@ -158,6 +567,32 @@ impl<P: LazySignalsArgs> Command for CreateEffectCommand<P> {
     }
 }
 
+/// Command to create an effect that runs a one-shot Bevy system from the given entity.
+pub struct CreateEffectSystemCommand<P: LazySignalsArgs> {
+    effect: Entity,
+    system: BoxedSystem<P>,
+    sources: Vec<Entity>,
+    triggers: Vec<Entity>,
+}
+
+impl<P: LazySignalsArgs> Command for CreateEffectSystemCommand<P> {
+    fn apply(self, world: &mut World) {
+        // register the system so the effect can run it by SystemId
+        let system = world.register_boxed_system(self.system);
+        world
+            .get_entity_mut(self.effect)
+            .unwrap()
+            .insert((
+                EffectBundle::from_function::<P>(
+                    EffectContext::Short(make_effect_system_with(system)),
+                    self.sources,
+                    self.triggers
+                ),
+                EffectSystem { system: system.entity() },
+            ));
+    }
+}
+
 /// Command to create a state (LazyImmutableImmutable) from the given entity.
 pub struct CreateStateCommand<T: LazySignalsData> {
     state: Entity,
@@ -199,6 +634,216 @@ impl<P: LazySignalsArgs> Command for CreateTaskCommand<P> {
     }
 }
 
+/// Command to create a computed memo that tracks its own sources from the given entity.
+/// There is nothing to subscribe to until it runs, so it is marked to compute right away.
+pub struct CreateTrackedComputedCommand<R: LazySignalsData> {
+    computed: Entity,
+    function: Mutex<Box<dyn ComputedContext>>,
+    tracked: Arc<Mutex<Vec<Entity>>>,
+    result_type: PhantomData<R>,
+}
+
+impl<R: LazySignalsData> Command for CreateTrackedComputedCommand<R> {
+    fn apply(self, world: &mut World) {
+        let component_id = world.init_component::<LazySignalsState<R>>();
+        world
+            .get_entity_mut(self.computed)
+            .unwrap()
+            .insert((
+                ComputedBundle::<R>::from_function::<()>(self.function, vec![], component_id),
+                TrackedSources { read: self.tracked },
+                ComputeMemo,
+            ));
+    }
+}
+
+/// Command to create an effect that tracks its own sources from the given entity.
+/// There is nothing to subscribe to until it runs, so it is triggered right away.
+pub struct CreateTrackedEffectCommand {
+    effect: Entity,
+    function: Mutex<Box<dyn EffectWrapper>>,
+    tracked: Arc<Mutex<Vec<Entity>>>,
+    triggers: Vec<Entity>,
+}
+
+impl Command for CreateTrackedEffectCommand {
+    fn apply(self, world: &mut World) {
+        world
+            .get_entity_mut(self.effect)
+            .unwrap()
+            .insert((
+                EffectBundle::from_function::<()>(
+                    EffectContext::Short(self.function),
+                    vec![],
+                    self.triggers
+                ),
+                TrackedSources { read: self.tracked },
+                DeferredEffect,
+                TriggeredEffect,
+            ));
+    }
+}
+
+/// Command to cancel a task (drop its running Task) from the given entity.
+pub struct CancelTaskCommand {
+    task: Entity,
+}
+
+impl Command for CancelTaskCommand {
+    fn apply(self, world: &mut World) {
+        trace!("CancelTaskCommand {:?}", self.task);
+        // nothing to do if the task already finished or the entity is gone
+        let Some(mut entity) = world.get_entity_mut(self.task) else {
+            return;
+        };
+        let running = entity.take::<RunningTask>().is_some();
+
+        // also forget any retrigger queued while it was running
+        entity.remove::<TriggeredEffect>();
+
+        // an async computed would otherwise stay Loading
+        let reset = entity.get::<AsyncComputedReset>().map(|reset| reset.function.clone());
+        if let (true, Some(reset)) = (running, reset) {
+            reset(world);
+        }
+    }
+}
+
+/// Command to tear down a signal, computed, effect, or task.
+pub struct DestroySignalCommand {
+    signal: Entity,
+    policy: TeardownPolicy,
+}
+
+/// Find every computed and effect that lists the entity as a source or trigger.
+fn dependents(entity: Entity, world: &mut World) -> Vec<Entity> {
+    let mut dependents = Vec::<Entity>::new();
+    let mut query_computeds = world.query::<(Entity, &ComputedImmutable)>();
+    for (dependent, computed) in query_computeds.iter(world) {
+        if computed.sources.contains(&entity) {
+            dependents.push(dependent);
+        }
+    }
+    let mut query_effects = world.query::<(Entity, &LazyEffect)>();
+    for (dependent, effect) in query_effects.iter(world) {
+        if effect.sources.contains(&entity) || effect.triggers.contains(&entity) {
+            dependents.push(dependent);
+        }
+    }
+    dependents
+}
+
+impl Command for DestroySignalCommand {
+    fn apply(self, world: &mut World) {
+        trace!("DestroySignalCommand {:?}", self.signal);
+        // cloned rather than scoped, in case this was flushed from inside an effect
+        let Some(type_registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+            error!("Could not destroy {:?}, AppTypeRegistry is not available", self.signal);
+            record_error(world, self.signal, LazySignalsError::ReflectError(self.signal));
+            return;
+        };
+        let type_registry = type_registry.read();
+        let mut errors = Vec::<(Entity, LazySignalsError)>::new();
+        let mut destroyed = Vec::<Entity>::new();
+        let mut stack = vec![self.signal];
+        while let Some(entity) = stack.pop() {
+            // might already be gone (e.g. reached twice while cascading)
+            let Some(handle) = world.get_entity(entity) else {
+                continue;
+            };
+
+            // whatever this entity was subscribed to
+            let mut sources = Vec::<Entity>::new();
+            if let Some(computed) = handle.get::<ComputedImmutable>() {
+                sources.extend(computed.sources.iter());
+            }
+            if let Some(effect) = handle.get::<LazyEffect>() {
+                sources.extend(effect.sources.iter());
+                sources.extend(effect.triggers.iter());
+            }
+
+            // stop being notified by the sources
+            for source in sources.iter() {
+                if let Err(error) = unsubscribe(&entity, source, &type_registry, world) {
+                    errors.push((entity, error));
+                }
+            }
+
+            // a watch of it ends its stream with an error and goes away, whatever the policy
+            // (its effect has no state to merge an error into, so it would just sit there)
+            let watchers = world
+                .query::<(Entity, &WatchGuard)>()
+                .iter(world)
+                .filter(|(_, guard)| guard.watched == entity)
+                .map(|(watcher, guard)| {
+                    (guard.close)(LazySignalsError::MissingSource(entity));
+                    watcher
+                })
+                .collect::<Vec<Entity>>();
+            stack.extend(watchers.iter());
+
+            // decide what happens to anything downstream
+            let dependents = dependents(entity, world);
+            match self.policy {
+                TeardownPolicy::Cascade => {
+                    stack.extend(dependents);
+                }
+                TeardownPolicy::ErrorOut => {
+                    for dependent in dependents {
+                        if watchers.contains(&dependent) {
+                            continue;
+                        }
+                        let error = LazySignalsError::MissingSource(entity);
+                        if
+                            let Err(error) = send_error(
+                                &dependent,
+                                error.clone(),
+                                &type_registry,
+                                world
+                            )
+                        {
+                            errors.push((dependent, error));
+                        }
+                        errors.push((dependent, error));
+                    }
+                }
+            }
+
+            // the one-shot system of an effect system goes away with it
+            if let Some(effect_system) = world.entity(entity).get::<EffectSystem>() {
+                let system = effect_system.system;
+                world.despawn(system);
+            }
+
+            // dropping the receivers that feed it disconnects their SignalSenders
+            let receivers = world
+                .query::<(Entity, &SignalReceiver, Has<LazyEffect>)>()
+                .iter(world)
+                .filter(|(_, receiver, _)| receiver.target == entity)
+                .map(|(receiver, _, is_task)| (receiver, is_task))
+                .collect::<Vec<(Entity, bool)>>();
+            for (receiver, is_task) in receivers {
+                if is_task {
+                    world.entity_mut(receiver).remove::<SignalReceiver>();
+                } else {
+                    world.despawn(receiver);
+                }
+            }
+
+            // dropping the Task cancels it, but be explicit about it
+            let mut handle = world.entity_mut(entity);
+            handle.remove::<RunningTask>();
+            handle.despawn();
+            destroyed.push(entity);
+        }
+
+        for (entity, error) in errors {
+            record_error(world, entity, error);
+        }
+        clear_errors(world, &destroyed);
+    }
+}
+
 /// Command to send a Signal (i.e. update a LazyImmutable during the next tick) to the given entity.
 pub struct SendSignalCommand<T: LazySignalsData> {
     signal: Entity,
@@ -217,9 +862,176 @@ impl<T: LazySignalsData> Command for SendSignalCommand<T> {
                 trace!("merged next and inserted SendSignal");
             } else {
                 error!("could not get Immutable");
+                record_error(world, self.signal, LazySignalsError::ReadError(self.signal));
             }
         } else {
             error!("could not get Signal");
+            record_error(world, self.signal, LazySignalsError::NoSignalError);
+        }
+    }
+}
+
+/// Command to set the EqualityStrategy of a state or computed on the given entity.
+pub struct SetEqualityCommand<T: LazySignalsData> {
+    state: Entity,
+    equality: EqualityStrategy<T>,
+}
+
+impl<T: LazySignalsData> Command for SetEqualityCommand<T> {
+    fn apply(self, world: &mut World) {
+        if let Some(mut entity) = world.get_entity_mut(self.state) {
+            if let Some(mut immutable) = entity.get_mut::<LazySignalsState<T>>() {
+                immutable.set_equality(self.equality);
+            } else {
+                error!("could not get State");
+                record_error(world, self.state, LazySignalsError::ReadError(self.state));
+            }
+        } else {
+            error!("could not get Signal");
+            record_error(world, self.state, LazySignalsError::NoSignalError);
+        }
+    }
+}
+
+/// Command to set the TaskRetriggerPolicy and TaskTimeout of the task on the given entity.
+/// A run that is already going keeps the deadline it started with.
+pub struct SetTaskOptionsCommand {
+    task: Entity,
+    options: TaskOptions,
+}
+
+impl Command for SetTaskOptionsCommand {
+    fn apply(self, world: &mut World) {
+        let Some(mut entity) = world.get_entity_mut(self.task) else {
+            error!("could not get Task");
+            record_error(world, self.task, LazySignalsError::NoSignalError);
+            return;
+        };
+        entity.insert(self.options.policy);
+        match self.options.timeout {
+            Some(duration) => {
+                entity.insert(TaskTimeout { duration });
+            }
+            None => {
+                entity.remove::<TaskTimeout>();
+            }
+        }
+    }
+}
+
+/// Command to replace the sources and triggers of the computed or effect on the given entity.
+/// The new sources are checked against the args tuple (and for cycles) first, and if they don't
+/// pass, the error is recorded and nothing changes. Otherwise it is unsubscribed from the old ones
+/// right away and goes through InitDependencies again to subscribe to the new ones. A computed
+/// has no triggers, so those are ignored. A tracked computed or effect keeps finding its own
+/// sources, so only its triggers change, and it is subscribed to those right away instead.
+pub struct SetSourcesCommand {
+    entity: Entity,
+    sources: Vec<Entity>,
+    triggers: Vec<Entity>,
+    recompute: bool,
+}
+
+impl Command for SetSourcesCommand {
+    fn apply(self, world: &mut World) {
+        trace!("SetSourcesCommand {:?}", self.entity);
+        let Some(handle) = world.get_entity(self.entity) else {
+            error!("could not get Signal");
+            record_error(world, self.entity, LazySignalsError::NoSignalError);
+            return;
+        };
+        let tracked = handle.contains::<TrackedSources>();
+
+        // whatever this entity is subscribed to now, and what it would be subscribed to instead
+        let mut old = Vec::<Entity>::new();
+        let mut new = Vec::<Entity>::new();
+        let args_info;
+        if let Some(computed) = handle.get::<ComputedImmutable>() {
+            if !tracked {
+                old.extend(computed.sources.iter());
+                new.extend(self.sources.iter());
+            }
+            args_info = computed.args_info;
+        } else if let Some(effect) = handle.get::<LazyEffect>() {
+            if !tracked {
+                old.extend(effect.sources.iter());
+                new.extend(self.sources.iter());
+            }
+
+            // a tracked effect stays subscribed to an old trigger that it still reads
+            old.extend(
+                effect.triggers
+                    .iter()
+                    .filter(|trigger| !tracked || !effect.sources.contains(trigger))
+            );
+            new.extend(self.triggers.iter());
+            args_info = effect.args_info;
+        } else {
+            error!("{:?} is not a computed or an effect", self.entity);
+            record_error(world, self.entity, LazySignalsError::ReadError(self.entity));
+            return;
+        }
+
+        // check the new sources before letting go of the old ones, so a refused rewire leaves the
+        // computed or effect working just like it was
+        if let Some(path) = find_cycle(&self.entity, &new, world) {
+            error!("Not rewiring {:?}, cycle detected: {:?}", self.entity, path);
+            record_error(world, self.entity, LazySignalsError::Cycle(path));
+            return;
+        }
+        if !tracked {
+            if let Err(error) = check_args(&self.sources, args_info, world) {
+                error!("Not rewiring {:?}: {}", self.entity, error);
+                record_error(world, self.entity, error);
+                return;
+            }
+        }
+
+        // cloned rather than scoped, in case this was flushed from inside an effect
+        let Some(type_registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+            error!("Could not rewire {:?}, AppTypeRegistry is not available", self.entity);
+            record_error(world, self.entity, LazySignalsError::ReflectError(self.entity));
+            return;
+        };
+        let type_registry = type_registry.read();
+
+        // stop being notified by the old ones
+        for source in old.iter() {
+            if let Err(error) = unsubscribe(&self.entity, source, &type_registry, world) {
+                record_error(world, self.entity, error);
+            }
+        }
+
+        // init_computeds and init_effects subscribe to the new ones, except for a tracked one,
+        // whose sources would not line up with an args tuple there
+        let mut handle = world.entity_mut(self.entity);
+        if let Some(mut computed) = handle.get_mut::<ComputedImmutable>() {
+            if !tracked {
+                computed.sources = self.sources;
+                handle.insert(InitDependencies);
+            }
+            if self.recompute {
+                handle.insert(ComputeMemo);
+            }
+        } else if let Some(mut effect) = handle.get_mut::<LazyEffect>() {
+            if !tracked {
+                effect.sources = self.sources;
+            }
+            effect.triggers = self.triggers.clone();
+            if self.recompute {
+                handle.insert((DeferredEffect, TriggeredEffect));
+            }
+            if !tracked {
+                handle.insert(InitDependencies);
+                return;
+            }
+
+            // a tracked effect is subscribed to its new triggers right here instead
+            for trigger in self.triggers.iter() {
+                if let Err(error) = subscribe(&self.entity, trigger, &type_registry, world) {
+                    record_error(world, self.entity, error);
+                }
+            }
         }
     }
 }
@@ -242,9 +1054,36 @@ impl<T: LazySignalsData> Command for TriggerSignalCommand<T> {
                 trace!("merged next and inserted SendSignal");
             } else {
                 error!("could not get State");
+                record_error(world, self.signal, LazySignalsError::ReadError(self.signal));
             }
         } else {
             error!("could not get Signal");
+            record_error(world, self.signal, LazySignalsError::NoSignalError);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_the_result_of_a_superseded_run() {
+        let mut world = World::new();
+        let component_id = world.init_component::<LazySignalsState<AsyncValue<u32>>>();
+        let computed = world
+            .spawn(StateBundle::<AsyncValue<u32>>::from_value(AsyncValue::Loading, component_id))
+            .id();
+        let latest = Arc::new(AtomicU64::new(2));
+
+        let value = AsyncValue::Ready(1u32);
+        StoreAsyncValueCommand { computed, generation: 1, latest: latest.clone(), value }.apply(
+            &mut world
+        );
+        assert!(!world.entity(computed).contains::<SendSignal>());
+
+        let value = AsyncValue::Ready(2u32);
+        StoreAsyncValueCommand { computed, generation: 2, latest, value }.apply(&mut world);
+        assert!(world.entity(computed).contains::<SendSignal>());
+    }
+}
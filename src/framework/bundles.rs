@@ -18,11 +18,12 @@ impl<R: LazySignalsData> ComputedBundle<R> {
     ) -> ComputedBundle<R> {
         ComputedBundle::<R> {
             state: LazySignalsState::<R>::new(None),
-            meta: ImmutableState { component_id },
+            meta: ImmutableState::new::<R>(component_id),
             context: ComputedImmutable {
                 function,
                 sources,
                 args_type: TypeId::of::<P>(),
+                args_info: P::type_info(),
                 result_type: TypeId::of::<LazySignalsState<R>>(),
             },
             init: InitDependencies,
@@ -48,6 +49,7 @@ impl EffectBundle {
                 sources,
                 triggers,
                 args_type: TypeId::of::<P>(),
+                args_info: P::type_info(),
             },
             init: InitDependencies,
         }
@@ -62,9 +64,13 @@ pub struct StateBundle<T: LazySignalsData> {
 
 impl<T: LazySignalsData> StateBundle<T> {
     pub fn from_value(data: T, component_id: ComponentId) -> StateBundle<T> {
+        StateBundle::from_result(Some(Ok(data)), component_id)
+    }
+
+    pub fn from_result(data: LazySignalsResult<T>, component_id: ComponentId) -> StateBundle<T> {
         StateBundle {
-            state: LazySignalsState::<T>::new(Some(Ok(data))),
-            meta: ImmutableState { component_id },
+            state: LazySignalsState::<T>::new(data),
+            meta: ImmutableState::new::<T>(component_id),
         }
     }
 }
@@ -33,17 +33,26 @@ pub trait LazySignalsObservable {
     /// Copy the data into a dynamic tuple of args for the Effect or Propagator to consume.
     fn copy_data(&mut self, caller: Entity, args: &mut DynamicTuple);
 
+    /// Get the error currently stored as the data, if any.
+    fn error(&self) -> Option<LazySignalsError>;
+
     /// Get the list of subscriber Entities that may need notification.
     fn get_subscribers(&self) -> Vec<Entity>;
 
     /// This method merges the next_value and returns get_subscribers().
     fn merge(&mut self) -> MaybeFlaggedEntities;
 
+    /// Provide an error as the next value for the lazy update system to merge.
+    fn merge_error(&mut self, error: LazySignalsError);
+
     /// Called by a lazy update system to refresh the subscribers.
     fn merge_subscribers(&mut self);
 
     /// Called by an Effect or Memo indirectly by reading the current value.
     fn subscribe(&mut self, entity: Entity);
+
+    /// Called when an Effect or Memo is torn down so it will no longer be notified.
+    fn unsubscribe(&mut self, entity: Entity);
 }
 
 /// A LazySignalsState is known as a cell in a propagator network. It may also be referred to as
@@ -57,6 +66,8 @@ pub trait LazySignalsObservable {
 /// The subscriber set is built from the sources/triggers of computed memos and effects, so it does
 /// not have to be serialized, which is good because the SparseSet doesn't seem to do Reflect.
 ///
+/// Whether a new value counts as a change is up to its EqualityStrategy (PartialEq by default).
+///
 /// This LazySignalsState component is lazy. Other forms are left as an exercise for the reader.
 #[derive(Component, Reflect)]
 #[reflect(Component, LazySignalsObservable)]
@@ -68,6 +79,8 @@ pub struct LazySignalsState<T: LazySignalsData> {
     subscribers: EntitySet,
     #[reflect(ignore)]
     next_subscribers: EntitySet,
+    #[reflect(ignore)]
+    equality: EqualityStrategy<T>,
 }
 
 impl<T: LazySignalsData> LazySignalsState<T> {
@@ -78,8 +91,14 @@ impl<T: LazySignalsData> LazySignalsState<T> {
             triggered: false,
             subscribers: empty_set(),
             next_subscribers: empty_set(),
+            equality: EqualityStrategy::default(),
         }
     }
+
+    /// Change how this state decides whether a new value is different from the current one.
+    pub fn set_equality(&mut self, equality: EqualityStrategy<T>) {
+        self.equality = equality;
+    }
 }
 
 impl<T: LazySignalsData> LazySignalsImmutable for LazySignalsState<T> {
@@ -91,7 +110,10 @@ impl<T: LazySignalsData> LazySignalsImmutable for LazySignalsState<T> {
     }
 
     fn update(&mut self, next: LazySignalsResult<Self::DataType>) -> bool {
-        let changed = self.data != next;
+        let changed = match (&self.data, &next) {
+            (Some(Ok(data)), Some(Ok(next))) => self.equality.changed(data, next),
+            _ => matches!(self.equality, EqualityStrategy::Always) || self.data != next,
+        };
         self.data = next;
         changed
     }
@@ -125,6 +147,13 @@ impl<T: LazySignalsData> LazySignalsObservable for LazySignalsState<T> {
         self.subscribe(caller);
     }
 
+    fn error(&self) -> Option<LazySignalsError> {
+        match &self.data {
+            Some(Err(error)) => Some(error.clone()),
+            _ => None,
+        }
+    }
+
     fn get_subscribers(&self) -> Vec<Entity> {
         let mut subs = Vec::<Entity>::new();
 
@@ -153,7 +182,7 @@ impl<T: LazySignalsData> LazySignalsObservable for LazySignalsState<T> {
                 if let Some(Ok(data)) = self.data.clone() {
                     trace!("data exists");
 
-                    if data != next {
+                    if self.equality.changed(&data, &next) {
                         trace!("data != next");
                         changed = true;
                         doo_eet = true;
@@ -200,6 +229,10 @@ impl<T: LazySignalsData> LazySignalsObservable for LazySignalsState<T> {
         Some((subs, changed, triggered))
     }
 
+    fn merge_error(&mut self, error: LazySignalsError) {
+        self.next_value = Some(Err(error));
+    }
+
     fn merge_subscribers(&mut self) {
         for subscriber in self.next_subscribers.indices() {
             self.subscribers.insert(subscriber, ());
@@ -210,4 +243,9 @@ impl<T: LazySignalsData> LazySignalsObservable for LazySignalsState<T> {
     fn subscribe(&mut self, entity: Entity) {
         self.next_subscribers.insert(entity, ());
     }
+
+    fn unsubscribe(&mut self, entity: Entity) {
+        self.subscribers.remove(entity);
+        self.next_subscribers.remove(entity);
+    }
 }
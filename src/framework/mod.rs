@@ -1,15 +1,40 @@
-use std::{ any::TypeId, fmt::Debug, sync::Mutex };
+use std::{
+    any::TypeId,
+    fmt::Debug,
+    sync::{ mpsc::{ Receiver, Sender }, Arc, Mutex },
+    time::Duration,
+};
 
 use bevy::{
     ecs::{ component::{ ComponentId, ComponentInfo }, storage::SparseSet, world::CommandQueue },
     prelude::*,
-    reflect::{ DynamicTuple, GetTypeRegistration, Tuple },
+    utils::HashMap,
+    reflect::{
+        Array,
+        DynamicArray,
+        DynamicEnum,
+        DynamicList,
+        DynamicMap,
+        DynamicStruct,
+        DynamicTuple,
+        DynamicTupleStruct,
+        Enum,
+        GetTypeRegistration,
+        List,
+        Map,
+        Struct,
+        Tuple,
+        TupleStruct,
+        TypeInfo,
+        Typed,
+    },
     tasks::Task,
+    utils::Instant,
 };
 
 use thiserror::Error;
 
-use crate::LazySignalsObservable;
+use crate::{ api::TrackingContext, LazySignalsObservable };
 
 pub mod bundles;
 pub mod lazy_immutable;
@@ -22,9 +47,67 @@ pub type LazySignalsResult<R> = Option<Result<R, LazySignalsError>>;
 /// Return type for returning an optional list of entities and some flags (changed, triggered).
 pub type MaybeFlaggedEntities = Option<(Vec<Entity>, bool, bool)>;
 
+/// Wraps a dynamic reflect type (DynamicStruct, DynamicList, etc.) so it can be signal data.
+/// The dynamic types are not Clone or PartialEq, so this clones with clone_dynamic and compares
+/// with reflect_partial_eq (two values that can't be compared are never equal).
+#[derive(Reflect)]
+#[reflect_value(Debug, PartialEq)]
+pub struct LazySignalsDynamic<D: LazySignalsDynamicValue>(pub D);
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Implemented only for the bevy_reflect Dynamic* types that LazySignalsDynamic can wrap.
+pub trait LazySignalsDynamicValue: sealed::Sealed + Reflect + TypePath {
+    fn clone_dynamic_value(&self) -> Self;
+}
+
+macro_rules! impl_dynamic_value {
+    ($($dynamic:ty => $reflect:path),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $dynamic {}
+
+            impl LazySignalsDynamicValue for $dynamic {
+                fn clone_dynamic_value(&self) -> Self {
+                    <$dynamic as $reflect>::clone_dynamic(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_dynamic_value! {
+    DynamicArray => Array,
+    DynamicEnum => Enum,
+    DynamicList => List,
+    DynamicMap => Map,
+    DynamicStruct => Struct,
+    DynamicTuple => Tuple,
+    DynamicTupleStruct => TupleStruct,
+}
+
+impl<D: LazySignalsDynamicValue> Clone for LazySignalsDynamic<D> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_dynamic_value())
+    }
+}
+
+impl<D: LazySignalsDynamicValue> PartialEq for LazySignalsDynamic<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.reflect_partial_eq(&other.0).unwrap_or(false)
+    }
+}
+
+impl<D: LazySignalsDynamicValue> Debug for LazySignalsDynamic<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.debug(f)
+    }
+}
+
 /// ## Enums
 /// Read error.
-#[derive(Error, Clone, Copy, PartialEq, Reflect, Debug)]
+#[derive(Error, Clone, PartialEq, Reflect, Debug)]
 pub enum LazySignalsError {
     /// An attempt was made to reference a LazySignals entity that does not exist.
     #[error["Signal does not exist"]]
@@ -37,6 +120,63 @@ pub enum LazySignalsError {
     /// An attempt was made to read a signal and something weird went wrong.
     #[error("Error reading signal {0}")]
     ReadError(Entity),
+
+    /// A computed or effect would (transitively) depend on itself. Holds the offending path.
+    #[error("Cycle detected: {0:?}")]
+    Cycle(Vec<Entity>),
+
+    /// Propagation was stopped after going over a budget in LazySignalsLimits.
+    #[error("Propagation budget of {0} exceeded")]
+    BudgetExceeded(usize),
+
+    /// A signal was sent more often in one frame than allowed (probably a feedback loop).
+    #[error("Signal {0} sent more than {1} times in one frame")]
+    RunawaySignal(Entity, usize),
+
+    /// The LazySignalsState of an entity could not be reflected (probably not registered).
+    #[error("Could not reflect signal {0}, is its type registered?")]
+    ReflectError(Entity),
+
+    /// The args tuple of a computed or effect does not match the types of its sources.
+    #[error("Arg {index} should be {expected} but found {found}")]
+    ArgsTypeMismatch {
+        index: usize,
+        expected: String,
+        found: String,
+    },
+
+    /// A source was destroyed (or despawned by other code) while something still depended on it.
+    #[error("Source {0} does not exist")]
+    MissingSource(Entity),
+
+    /// A Bevy system registered as an effect could not be run.
+    #[error("Could not run system {0}")]
+    SystemError(Entity),
+
+    /// A task was still running when its TaskTimeout ran out, so it was dropped.
+    #[error("Task {0} timed out")]
+    TaskTimedOut(Entity),
+
+    /// A component field path (e.g. Transform.translation.x) could not be resolved or written.
+    #[error("Could not resolve component path {0}")]
+    InvalidPath(String),
+}
+
+/// The value an async computed keeps in its LazySignalsState while its future runs.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub enum AsyncValue<R: LazySignalsData> {
+    /// Nothing has run yet.
+    Idle,
+
+    /// The future is running (the last Ready value, if any, is gone).
+    Loading,
+
+    /// The future returned a value.
+    Ready(R),
+
+    /// The future returned an error, stored as its Display string.
+    /// (also stored as "cancelled" if the running future is cancelled with cancel_task)
+    Failed(String),
 }
 
 // ## Traits
@@ -64,8 +204,8 @@ impl<T> LazySignalsData
             'static {}
 
 /// A tuple containing parameters for a computed memo or effect.
-pub trait LazySignalsArgs: LazySignalsData + Tuple {}
-impl<T> LazySignalsArgs for T where T: LazySignalsData + Tuple {}
+pub trait LazySignalsArgs: LazySignalsData + Tuple + Typed {}
+impl<T> LazySignalsArgs for T where T: LazySignalsData + Tuple + Typed {}
 
 /// A Propagator function aggregates (merges) data from multiple cells to store in a bound cell.
 /// Compared to the MIT model, the Computed pulls data into a cell they are bound to.
@@ -81,6 +221,10 @@ impl<T> LazySignalsArgs for T where T: LazySignalsData + Tuple {}
 pub trait ComputedContext: Send + Sync + FnMut(&DynamicTuple, &Entity, &mut World) -> bool {}
 impl<T: Send + Sync + FnMut(&DynamicTuple, &Entity, &mut World) -> bool> ComputedContext for T {}
 
+/// Tells whether anything a Bevy system reads has changed since the system last ran.
+pub trait SystemChangeDetector: Send + Sync + Fn(&World) -> bool {}
+impl<T: Send + Sync + Fn(&World) -> bool> SystemChangeDetector for T {}
+
 /// Let the developer pass in a regular Rust closure that borrows a concrete typed tuple as args.
 /// The return type is a LazySignalsResult which can then be memoized.
 pub trait Computed<P: LazySignalsArgs, R: LazySignalsData>: Send +
@@ -94,20 +238,142 @@ impl<
 > Computed<P, R> for T {}
 
 /// This is the same basic thing but this fn just runs side-effects so no value is returned.
-pub trait EffectWrapper: Send + Sync + FnMut(&DynamicTuple, &mut World) {}
-impl<T: Send + Sync + FnMut(&DynamicTuple, &mut World)> EffectWrapper for T {}
+/// (it may still fail to run at all, e.g. if the args don't line up)
+pub trait EffectWrapper: Send +
+    Sync +
+    FnMut(&DynamicTuple, &mut World) -> Result<(), LazySignalsError> {}
+impl<
+    T: Send + Sync + FnMut(&DynamicTuple, &mut World) -> Result<(), LazySignalsError>
+> EffectWrapper for T {}
 
 /// Let the developer pass in a regular Rust closure that borrows a concrete typed tuple as args.
 pub trait Effect<P: LazySignalsArgs>: Send + Sync + 'static + FnMut(P, &mut World) {}
 impl<P: LazySignalsArgs, T: Send + Sync + 'static + FnMut(P, &mut World)> Effect<P> for T {}
 
-pub trait TaskWrapper: Send + Sync + Fn(&DynamicTuple) -> Task<CommandQueue> {}
-impl<T: Send + Sync + Fn(&DynamicTuple) -> Task<CommandQueue>> TaskWrapper for T {}
+pub trait TaskWrapper: Send +
+    Sync +
+    Fn(&DynamicTuple) -> Result<Task<CommandQueue>, LazySignalsError> {}
+impl<
+    T: Send + Sync + Fn(&DynamicTuple) -> Result<Task<CommandQueue>, LazySignalsError>
+> TaskWrapper for T {}
+
+/// Let the developer pass in a closure that returns a future resolving to the value to store.
+pub trait AsyncComputed<P: LazySignalsArgs, F>: Send + Sync + 'static + Fn(P) -> F {}
+impl<P: LazySignalsArgs, F, T: Send + Sync + 'static + Fn(P) -> F> AsyncComputed<P, F> for T {}
 
 pub trait AsyncTask<P: LazySignalsArgs>: Send + Sync + 'static + Fn(P) -> Task<CommandQueue> {}
 impl<P: LazySignalsArgs, T: Send + Sync + 'static + Fn(P) -> Task<CommandQueue>> AsyncTask<P>
 for T {}
 
+/// A task that also gets a SignalSender to push intermediate values (e.g. progress) while it runs.
+pub trait StreamingTask<P: LazySignalsArgs, T: LazySignalsData>: Send +
+    Sync +
+    'static +
+    Fn(P, SignalSender<T>) -> Task<CommandQueue> {}
+impl<
+    P: LazySignalsArgs,
+    T: LazySignalsData,
+    U: Send + Sync + 'static + Fn(P, SignalSender<T>) -> Task<CommandQueue>
+> StreamingTask<P, T> for U {}
+
+/// Let the developer pass in a closure that reads whatever it needs through a TrackingContext
+/// instead of taking a tuple of args. Whatever it read becomes its sources.
+pub trait TrackedComputed<R: LazySignalsData>: Send +
+    Sync +
+    'static +
+    Fn(&mut TrackingContext) -> LazySignalsResult<R> {}
+impl<
+    R: LazySignalsData,
+    T: Send + Sync + 'static + Fn(&mut TrackingContext) -> LazySignalsResult<R>
+> TrackedComputed<R> for T {}
+
+/// Same as TrackedComputed but just runs side-effects. The world is available through the context.
+pub trait TrackedEffect: Send + Sync + 'static + FnMut(&mut TrackingContext) {}
+impl<T: Send + Sync + 'static + FnMut(&mut TrackingContext)> TrackedEffect for T {}
+
+/// Drains the values sent through a SignalSender and sends them on to the target signal.
+/// Returns false once every SignalSender has been dropped.
+pub trait ReceiverWrapper: Send + FnMut(&mut Commands) -> bool {}
+impl<T: Send + FnMut(&mut Commands) -> bool> ReceiverWrapper for T {}
+
+/// Queues the error as the last item of a SignalStream, just before its effect is destroyed.
+pub trait WatchCloser: Send + Sync + Fn(LazySignalsError) {}
+impl<T: Send + Sync + Fn(LazySignalsError)> WatchCloser for T {}
+
+/// Sends the value of a mirrored component field if the component changed since the last run.
+/// Fails if the field can't be read, or with MissingSource once the target entity is gone.
+pub trait MirrorWrapper: Send +
+    Sync +
+    FnMut(&World, &mut Commands) -> Result<(), LazySignalsError> {}
+impl<
+    T: Send + Sync + FnMut(&World, &mut Commands) -> Result<(), LazySignalsError>
+> MirrorWrapper for T {}
+
+/// Returns true if two values of a state or computed should be treated as equal.
+pub trait EqualityComparator<T>: Send + Sync + Fn(&T, &T) -> bool {}
+impl<T, U: Send + Sync + Fn(&T, &T) -> bool> EqualityComparator<T> for U {}
+
+/// What happens to the computeds and effects that depend on a destroyed signal or computed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TeardownPolicy {
+    /// Dependents stay around but get a MissingSource error (computeds store it as their value).
+    #[default]
+    ErrorOut,
+
+    /// Dependents are destroyed too, all the way down.
+    Cascade,
+}
+
+/// What happens when a task is triggered while it is still running from last time.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TaskRetriggerPolicy {
+    /// Ignore the trigger, the task only becomes eligible to run again once it exits.
+    #[default]
+    Drop,
+
+    /// Remember the trigger and run once more (with the args at that time) after it exits.
+    QueueLatest,
+
+    /// Drop the running Task (which cancels it) and start a new one right away.
+    CancelAndRestart,
+}
+
+/// How a task behaves when it is retriggered or takes too long. The default drops triggers while
+/// it is running and never times out, same as a task made without options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TaskOptions {
+    pub policy: TaskRetriggerPolicy,
+
+    /// Any run that takes longer than this is dropped and a TaskTimedOut error is recorded.
+    pub timeout: Option<Duration>,
+}
+
+/// How a state or computed decides whether a new value is a change its subscribers should hear
+/// about. Applies to sent signals and to recomputed values alike.
+#[derive(Clone, Default)]
+pub enum EqualityStrategy<T: LazySignalsData> {
+    /// Compare with PartialEq.
+    #[default]
+    PartialEq,
+
+    /// Compare with a closure that returns true if the values are equal (e.g. within an epsilon).
+    Custom(Arc<dyn EqualityComparator<T>>),
+
+    /// Never equal, so every send (or recompute) notifies.
+    Always,
+}
+
+impl<T: LazySignalsData> EqualityStrategy<T> {
+    /// Whether the next value counts as a change from the current one.
+    pub fn changed(&self, data: &T, next: &T) -> bool {
+        match self {
+            EqualityStrategy::PartialEq => data != next,
+            EqualityStrategy::Custom(equal) => !equal(data, next),
+            EqualityStrategy::Always => true,
+        }
+    }
+}
+
 pub enum EffectContext {
     Short(Mutex<Box<dyn EffectWrapper>>),
     Long(Mutex<Box<dyn TaskWrapper>>),
@@ -131,12 +397,55 @@ impl<
         ) -> MaybeFlaggedEntities
 > ObservableFn for T {}
 
+/// ## Channels
+///
+/// Cloneable handle to send values to a signal from a task (or any other thread).
+/// The values are sent the next time the LazySignals systems run, and only the latest one counts.
+#[derive(Clone)]
+pub struct SignalSender<T: LazySignalsData> {
+    sender: Sender<T>,
+}
+
+impl<T: LazySignalsData> SignalSender<T> {
+    pub fn new(sender: Sender<T>) -> Self {
+        Self { sender }
+    }
+
+    /// Queue the value to be sent. Fails once the signal it feeds (or the task it belongs to) has
+    /// been destroyed, since that drops the receiving end.
+    pub fn send(&self, data: T) -> Result<(), LazySignalsError> {
+        self.sender.send(data).map_err(|_| LazySignalsError::NoSignalError)
+    }
+}
+
+/// ## Events
+///
+/// Sent for each error recorded in LazySignalsResource.errors, for systems that want to react.
+#[derive(Event, Clone, Debug)]
+pub struct LazySignalsErrorEvent {
+    pub entity: Entity,
+    pub error: LazySignalsError,
+}
+
 /// ## Component Structs
 ///
 /// An ImmutableState stores the ComponentId of a LazySignalsState<T> with concrete T.
+/// It also stores the type this shows up as in an args tuple (Option<T>) to check against.
 #[derive(Component)]
 pub struct ImmutableState {
     pub component_id: ComponentId,
+    pub arg_type: TypeId,
+    pub arg_type_path: &'static str,
+}
+
+impl ImmutableState {
+    pub fn new<T: LazySignalsData>(component_id: ComponentId) -> Self {
+        Self {
+            component_id,
+            arg_type: TypeId::of::<Option<T>>(),
+            arg_type_path: Option::<T>::type_path(),
+        }
+    }
 }
 
 /// A SendSignal component marks a LazySignalsState cell as having a next_value.
@@ -150,9 +459,24 @@ pub struct ComputedImmutable {
     pub function: Mutex<Box<dyn ComputedContext>>,
     pub sources: Vec<Entity>,
     pub args_type: TypeId,
+    pub args_info: &'static TypeInfo,
     pub result_type: TypeId,
 }
 
+/// A ComputedSystem marks a ComputedImmutable that runs a read-only Bevy system. Besides its
+/// sources, it is recomputed whenever the components and resources the system reads change.
+#[derive(Component)]
+pub struct ComputedSystem {
+    pub changed: Box<dyn SystemChangeDetector>,
+}
+
+/// An AsyncComputedReset stores an async computed's Failed("cancelled") value, sent when its
+/// running task is cancelled so the state doesn't stay Loading.
+#[derive(Component)]
+pub struct AsyncComputedReset {
+    pub function: Arc<dyn Fn(&mut World) + Send + Sync>,
+}
+
 /// A ComputeMemo component marks a Computed function that needs computin.
 #[derive(Component)]
 #[component(storage = "SparseSet")]
@@ -165,6 +489,13 @@ pub struct LazyEffect {
     pub sources: Vec<Entity>,
     pub triggers: Vec<Entity>,
     pub args_type: TypeId,
+    pub args_info: &'static TypeInfo,
+}
+
+/// An EffectSystem stores the entity of the one-shot Bevy system run by a LazyEffect.
+#[derive(Component)]
+pub struct EffectSystem {
+    pub system: Entity,
 }
 
 /// A DeferredEffect component marks an Effect function that needs to run.
@@ -173,15 +504,64 @@ pub struct LazyEffect {
 pub struct DeferredEffect;
 
 /// A TriggeredEffect component marks an Effect function that needs to run.
+/// (i.e. a task that was retriggered while running with TaskRetriggerPolicy::QueueLatest)
 #[derive(Component)]
 #[component(storage = "SparseSet")]
 pub struct TriggeredEffect;
 
 /// A RunningTask component marks an Effect function that may still be running.
+/// Removing it drops the Task, which cancels it.
 #[derive(Component)]
 #[component(storage = "SparseSet")]
 pub struct RunningTask {
     pub task: Task<CommandQueue>,
+    pub deadline: Option<Instant>,
+}
+
+/// A SignalReceiver drains the values pushed by a SignalSender (of a streaming task or not).
+/// (the Receiver end of the channel is not Sync, hence the Mutex)
+#[derive(Component)]
+pub struct SignalReceiver {
+    pub function: Mutex<Box<dyn ReceiverWrapper>>,
+    pub target: Entity,
+}
+
+/// A WatchGuard ties the effect feeding a SignalStream to the stream and to the watched entity.
+/// The effect is destroyed once the stream is dropped (which disconnects the receiver), or along
+/// with the watched entity whatever the TeardownPolicy, in which case close ends the stream with
+/// an error first. (the Receiver end of the channel is not Sync, hence the Mutex)
+#[derive(Component)]
+pub struct WatchGuard {
+    pub watched: Entity,
+    pub dropped: Mutex<Receiver<()>>,
+    pub close: Box<dyn WatchCloser>,
+}
+
+/// A ComponentPathMirror keeps a state in sync with a reflected field of a component.
+#[derive(Component)]
+pub struct ComponentPathMirror {
+    pub function: Mutex<Box<dyn MirrorWrapper>>,
+}
+
+/// TrackedSources marks a ComputedImmutable or LazyEffect whose sources are whatever its function
+/// read through a TrackingContext the last time it ran. The function fills in the list and the
+/// system that ran it swaps the subscriptions over.
+#[derive(Component)]
+pub struct TrackedSources {
+    pub read: Arc<Mutex<Vec<Entity>>>,
+}
+
+impl TrackedSources {
+    /// Take the list of sources read during the last run.
+    pub fn take(&self) -> Vec<Entity> {
+        std::mem::take(&mut *self.read.lock().unwrap())
+    }
+}
+
+/// A TaskTimeout limits how long each run of a task may take before it is dropped.
+#[derive(Component)]
+pub struct TaskTimeout {
+    pub duration: Duration,
 }
 
 /// Marks a ComputedImmutable or LazyEffect as needing to subscribe to its dependencies.
@@ -203,8 +583,9 @@ pub type EntityRelationshipSet = SparseSet<Entity, Vec<Entity>>;
 /// Set of unique Entities
 pub type EntitySet = SparseSet<Entity, ()>;
 
-/// Set of internal errors when running computed (propagator) and effect functions.
-pub type ErrorSet = SparseSet<Entity, LazySignalsError>;
+/// Map of internal errors when running computed (propagator) and effect functions.
+/// Keyed by the whole Entity (not just its index) so a recycled entity starts without errors.
+pub type ErrorSet = HashMap<Entity, LazySignalsError>;
 
 /// Create an empty sparse set for storing Entities by ID.
 pub fn empty_set() -> EntitySet {
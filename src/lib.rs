@@ -1,9 +1,17 @@
-use bevy::{ ecs::schedule::SystemConfigs, prelude::*, reflect::* };
+use bevy::{
+    core::FrameCount,
+    ecs::{ schedule::SystemConfigs, storage::SparseSet },
+    prelude::*,
+    reflect::*,
+    utils::HashMap,
+};
 
 mod arcane_wizardry;
 
 pub mod api;
 
+pub mod bindings;
+
 pub mod commands;
 
 pub mod framework;
@@ -12,31 +20,38 @@ use lazy_immutable::*;
 
 pub mod systems;
 use systems::{
-    computed::compute_memos,
+    computed::{ check_computed_systems, compute_memos },
     init::{ init_effects, init_computeds },
-    signal::send_signals,
+    signal::{ check_watches, mirror_component_paths, receive_signals, send_signals },
     effect::{ apply_deferred_effects, check_tasks },
+    error::send_error_events,
 };
 
 pub mod prelude {
-    pub use crate::{ api::*, framework::*, systems::*, LazySignalsPlugin };
+    pub use crate::{ api::*, bindings::*, framework::*, systems::*, LazySignalsPlugin };
 }
 
 /// Convenience typedefs.
-/// (could not get &String to work)
+/// (StaticStrRef is for literals, use LazySignalsString for text built at runtime)
 pub type StaticStrRef = &'static str;
 pub type LazySignalsBool = LazySignalsState<bool>;
 pub type LazySignalsInt = LazySignalsState<u32>;
 pub type LazySignalsFloat = LazySignalsState<f64>;
 pub type LazySignalsStr = LazySignalsState<StaticStrRef>;
 pub type LazySignalsUnit = LazySignalsState<()>; // for triggers, mostly
-pub type LazySignalsTuple = LazySignalsState<DynamicTuple>;
-pub type LazySignalsArray = LazySignalsState<DynamicArray>;
-pub type LazySignalsList = LazySignalsState<DynamicList>;
-pub type LazySignalsMap = LazySignalsState<DynamicMap>;
-pub type LazySignalsStruct = LazySignalsState<DynamicStruct>;
-pub type LazySignalsTupleStruct = LazySignalsState<DynamicTupleStruct>;
-pub type LazySignalsEnum = LazySignalsState<DynamicEnum>;
+pub type LazySignalsString = LazySignalsState<String>;
+pub type LazySignalsVec<T> = LazySignalsState<Vec<T>>;
+pub type LazySignalsHashMap<K, V> = LazySignalsState<HashMap<K, V>>;
+/// (the state of an async computed)
+pub type LazySignalsAsyncValue<T> = LazySignalsState<AsyncValue<T>>;
+/// (the dynamic types are for data whose shape is only known at runtime, e.g. from a script)
+pub type LazySignalsTuple = LazySignalsState<LazySignalsDynamic<DynamicTuple>>;
+pub type LazySignalsArray = LazySignalsState<LazySignalsDynamic<DynamicArray>>;
+pub type LazySignalsList = LazySignalsState<LazySignalsDynamic<DynamicList>>;
+pub type LazySignalsMap = LazySignalsState<LazySignalsDynamic<DynamicMap>>;
+pub type LazySignalsStruct = LazySignalsState<LazySignalsDynamic<DynamicStruct>>;
+pub type LazySignalsTupleStruct = LazySignalsState<LazySignalsDynamic<DynamicTupleStruct>>;
+pub type LazySignalsEnum = LazySignalsState<LazySignalsDynamic<DynamicEnum>>;
 
 /// A reference implementation follows. A developer can replace any or all pieces and provide a new
 /// plugin if so desired.
@@ -48,17 +63,60 @@ pub struct LazySignalsSystemSet;
 /// Convenience functions to make it easy to run the LazySignals systems when needed.
 pub fn lazy_signals_full_systems() -> SystemConfigs {
     (
+        receive_signals,
+        check_watches,
+        mirror_component_paths,
         check_tasks,
         init_effects,
         init_computeds,
+        check_computed_systems,
         send_signals,
         compute_memos,
         apply_deferred_effects,
+        send_error_events,
     ).chain()
 }
 
 pub fn lazy_signals_flush_systems() -> SystemConfigs {
-    (check_tasks, init_effects, init_computeds, send_signals, compute_memos).chain()
+    (
+        receive_signals,
+        check_watches,
+        mirror_component_paths,
+        check_tasks,
+        init_effects,
+        init_computeds,
+        check_computed_systems,
+        send_signals,
+        compute_memos,
+        send_error_events,
+    ).chain()
+}
+
+/// Budgets that stop runaway propagation cleanly instead of freezing the frame.
+/// Going over any of these records a diagnostic error in LazySignalsResource.errors.
+#[derive(Clone, Copy, Debug)]
+pub struct LazySignalsLimits {
+    /// Max number of passes up the subscriber tree during one run of send_signals.
+    pub max_iterations: usize,
+
+    /// Max number of memos popped off the stack during one run of compute_memos.
+    pub max_nodes: usize,
+
+    /// Max number of times any one signal can be sent in one frame (across flushes). An effect
+    /// that keeps re-sending the signals it depends on will eventually hit this.
+    /// Frames are counted with FrameCount (from FrameCountPlugin, part of MinimalPlugins and
+    /// DefaultPlugins). Without it, the counts reset on every run of send_signals.
+    pub max_sends_per_frame: usize,
+}
+
+impl Default for LazySignalsLimits {
+    fn default() -> Self {
+        Self {
+            max_iterations: 1024,
+            max_nodes: 65536,
+            max_sends_per_frame: 64,
+        }
+    }
 }
 
 /// Shared reactive context resource, aka global state.
@@ -75,18 +133,50 @@ pub struct LazySignalsResource {
     /// Tracks triggered entities (notify subscribers even if the value did not change).
     pub triggered: EntitySet,
 
-    /// Tracks errors that occur when things try to run.
+    /// Tracks errors that occur when things try to run. These persist across runs, until the
+    /// entity is destroyed.
     pub errors: ErrorSet,
+
+    /// Budgets for runaway-update protection. Insert the resource before adding the plugin to
+    /// configure them.
+    pub limits: LazySignalsLimits,
+
+    /// Counts how many times each signal was sent during the current frame.
+    sends: SparseSet<Entity, usize>,
+
+    /// The frame the send counts belong to (if FrameCount is available).
+    frame: Option<u32>,
+
+    /// Errors recorded since the last time LazySignalsErrorEvents were sent.
+    pending_errors: Vec<LazySignalsErrorEvent>,
 }
 
 /// This is a singleton that represents the "global state." It is used during internal updates.
 impl LazySignalsResource {
     /// Call this at the start of each run to make sure everything is fresh.
-    fn init(&mut self) {
+    /// Errors are left alone since some (e.g. cycles) are recorded before this runs.
+    /// Send counts are only reset once per frame (or every run if there is no FrameCount).
+    fn init(&mut self, frame: Option<u32>) {
         self.changed.clear();
         self.dirty.clear();
         self.triggered.clear();
-        self.errors.clear();
+        if frame.is_none() || frame != self.frame {
+            self.sends.clear();
+            self.frame = frame;
+        }
+    }
+
+    /// Record an error for the entity and queue a LazySignalsErrorEvent for it.
+    pub fn record_error(&mut self, entity: Entity, error: LazySignalsError) {
+        self.errors.insert(entity, error.clone());
+        self.pending_errors.push(LazySignalsErrorEvent { entity, error });
+    }
+
+    /// Count a send of the given signal and return false if it went over the frame budget.
+    fn count_send(&mut self, signal: Entity) -> bool {
+        let sends = self.sends.get(signal).copied().unwrap_or(0) + 1;
+        self.sends.insert(signal, sends);
+        sends <= self.limits.max_sends_per_frame
     }
 }
 
@@ -96,8 +186,44 @@ impl Default for LazySignalsResource {
             changed: empty_set(),
             dirty: empty_set(),
             triggered: empty_set(),
-            errors: ErrorSet::new(),
+            errors: ErrorSet::default(),
+            limits: LazySignalsLimits::default(),
+            sends: SparseSet::new(),
+            frame: None,
+            pending_errors: Vec::new(),
+        }
+    }
+}
+
+/// Errors recorded by commands while LazySignalsResource was not available (e.g. a command flushed
+/// from inside an effect, while the systems have the resource scoped). They are moved into
+/// LazySignalsResource.errors the next time send_error_events runs, and then the errors of any
+/// entities destroyed in the meantime are cleared.
+#[derive(Resource, Default)]
+pub struct LazySignalsDeferredErrors {
+    errors: Vec<(Entity, LazySignalsError)>,
+    cleared: Vec<Entity>,
+}
+
+/// Record an error for the entity, or keep it for later if LazySignalsResource is scoped.
+pub(crate) fn record_error(world: &mut World, entity: Entity, error: LazySignalsError) {
+    if let Some(mut signals) = world.get_resource_mut::<LazySignalsResource>() {
+        signals.record_error(entity, error);
+    } else {
+        let mut deferred = world.get_resource_or_insert_with(LazySignalsDeferredErrors::default);
+        deferred.errors.push((entity, error));
+    }
+}
+
+/// Forget the errors of destroyed entities, or do it later if LazySignalsResource is scoped.
+pub(crate) fn clear_errors(world: &mut World, entities: &[Entity]) {
+    if let Some(mut signals) = world.get_resource_mut::<LazySignalsResource>() {
+        for entity in entities {
+            signals.errors.remove(entity);
         }
+    } else {
+        let mut deferred = world.get_resource_or_insert_with(LazySignalsDeferredErrors::default);
+        deferred.cleared.extend_from_slice(entities);
     }
 }
 
@@ -110,22 +236,38 @@ impl Plugin for LazySignalsPlugin {
 
         // add the systems to process signals, memos, and effects
         app.init_resource::<LazySignalsResource>()
+            .init_resource::<LazySignalsDeferredErrors>()
+            .add_event::<LazySignalsErrorEvent>()
             // custom Immutable types must be manually registered
             .register_type::<LazySignalsBool>()
             .register_type::<LazySignalsInt>()
             .register_type::<LazySignalsFloat>()
             .register_type::<LazySignalsStr>()
             .register_type::<LazySignalsUnit>()
-            /*
+            .register_type::<LazySignalsString>()
+            .register_type::<LazySignalsVec<bool>>()
+            .register_type::<LazySignalsVec<u32>>()
+            .register_type::<LazySignalsVec<f64>>()
+            .register_type::<LazySignalsVec<StaticStrRef>>()
+            .register_type::<LazySignalsVec<()>>()
+            .register_type::<LazySignalsVec<String>>()
+            .register_type::<LazySignalsHashMap<String, bool>>()
+            .register_type::<LazySignalsHashMap<String, u32>>()
+            .register_type::<LazySignalsHashMap<String, f64>>()
+            .register_type::<LazySignalsHashMap<String, String>>()
+            .register_type::<LazySignalsAsyncValue<bool>>()
+            .register_type::<LazySignalsAsyncValue<u32>>()
+            .register_type::<LazySignalsAsyncValue<f64>>()
+            .register_type::<LazySignalsAsyncValue<StaticStrRef>>()
+            .register_type::<LazySignalsAsyncValue<()>>()
+            .register_type::<LazySignalsAsyncValue<String>>()
             .register_type::<LazySignalsTuple>()
             .register_type::<LazySignalsArray>()
             .register_type::<LazySignalsList>()
             .register_type::<LazySignalsMap>()
-            .register_type::<LazySignalsState>()
             .register_type::<LazySignalsStruct>()
             .register_type::<LazySignalsTupleStruct>()
             .register_type::<LazySignalsEnum>()
-            */
             .add_systems(
                 PreUpdate, // could be PostUpdate or whatever else (probably not Update)
                 // defaults to PreUpdate since it is assumed the UI will process right after Update
@@ -140,4 +282,11 @@ impl Plugin for LazySignalsPlugin {
                 lazy_signals_full_systems().in_set(LazySignalsSystemSet)
             );
     }
+
+    fn finish(&self, app: &mut App) {
+        // the per-frame send budget needs to know when a new frame starts
+        if !app.world().contains_resource::<FrameCount>() {
+            warn!("No FrameCount (see FrameCountPlugin), max_sends_per_frame is only checked per run");
+        }
+    }
 }
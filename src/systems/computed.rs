@@ -2,6 +2,28 @@ use bevy::{ ecs::world::World, prelude::*, reflect::DynamicTuple };
 
 use crate::{ arcane_wizardry::*, framework::*, LazySignalsResource };
 
+/// Mark computed systems for computation if anything they read changed since they last ran.
+/// send_signals then notifies their subscribers the same way it does for sources.
+/// (one that was refused at init is no longer a ComputedImmutable and never runs, so skip it)
+pub fn check_computed_systems(
+    world: &mut World,
+    query_systems: &mut QueryState<(Entity, &ComputedSystem), With<ComputedImmutable>>
+) {
+    trace!("COMPUTED SYSTEMS");
+
+    let mut changed = Vec::<Entity>::new();
+    query_systems.iter(world).for_each(|(entity, computed)| {
+        if (computed.changed)(world) {
+            trace!("-found computed system {:#?} with changed data", entity);
+            changed.push(entity);
+        }
+    });
+
+    for entity in changed {
+        world.entity_mut(entity).insert(ComputeMemo);
+    }
+}
+
 pub fn compute_memos(
     world: &mut World,
     query_memos: &mut QueryState<(Entity, &ImmutableState, &ComputedImmutable), With<ComputeMemo>>
@@ -30,14 +52,40 @@ pub fn compute_memos(
     });
 
     // main loop: evaluate highest index (pop the stack)
+    let mut count = 0;
     while let Some(computed) = stack.pop() {
+        // stop cleanly if evaluating the memos takes more steps than the budget allows
+        count += 1;
+        let max_nodes = world.resource::<LazySignalsResource>().limits.max_nodes;
+        if count > max_nodes {
+            error!("Stopped computing memos after {} steps", max_nodes);
+            stack.push(computed);
+            world.resource_scope(|world, mut signals: Mut<LazySignalsResource>| {
+                for computed in stack.drain(..) {
+                    signals.record_error(computed, LazySignalsError::BudgetExceeded(max_nodes));
+                    signals.dirty.remove(computed);
+                    if let Some(mut computed) = world.get_entity_mut(computed) {
+                        computed.remove::<ComputeMemo>();
+                    }
+                }
+            });
+            break;
+        }
+
         // do not run this Propagator if already in the processed set
         if processed.contains(computed) {
             continue;
         }
 
+        // the sources a tracked computed actually read this time
+        let mut retracked = None;
+
         world.resource_scope(|world, mut signals: Mut<LazySignalsResource>| {
-            let sources = sources.get(computed).unwrap();
+            // a dirty source that is no longer around (despawned elsewhere) is never coming back
+            let Some(sources) = sources.get(computed) else {
+                signals.dirty.remove(computed);
+                return;
+            };
             let mut dirty_sources = Vec::<Entity>::new();
             for source in sources {
                 let source = *source;
@@ -54,8 +102,24 @@ pub fn compute_memos(
                 // otherwise, if all sources are up to date, then recompute
 
                 // build component id -> info map (might already have some but be on the safe side)
+                let tracked = world.get::<TrackedSources>(computed).is_some();
+                let mut missing = None;
+                let mut found = Vec::<Entity>::new();
                 for source in sources.iter() {
-                    let immutable = world.entity(*source).get::<ImmutableState>().unwrap();
+                    // tolerate sources that were destroyed or despawned by other code
+                    let Some(immutable) = world
+                        .get_entity(*source)
+                        .and_then(|source| source.get::<ImmutableState>()) else {
+                        // a tracked computed just runs without it and stops depending on it
+                        // (TrackingContext::get returns None for it anyway)
+                        if tracked {
+                            trace!("-dropping missing source {:?} of {:?}", source, computed);
+                            continue;
+                        }
+                        missing = Some(*source);
+                        break;
+                    };
+                    found.push(*source);
                     let component_id = immutable.component_id;
                     trace!("-found a computed source with component ID {:#?}", component_id);
                     component_id_set.insert(*source, component_id);
@@ -65,7 +129,19 @@ pub fn compute_memos(
                 }
 
                 // remove the ComputeMemo component
-                world.entity_mut(computed).remove::<ComputeMemo>();
+                if let Some(mut handle) = world.get_entity_mut(computed) {
+                    handle.remove::<ComputeMemo>();
+                }
+
+                // don't run with a missing source, the args would not line up anyway
+                if let Some(source) = missing {
+                    error!("Not computing {:?}, source {:?} is missing", computed, source);
+                    signals.record_error(computed, LazySignalsError::MissingSource(source));
+                    processed.insert(computed, ());
+                    signals.dirty.remove(computed);
+                    return;
+                }
+                let sources = &found;
 
                 world.resource_scope(|world, type_registry: Mut<AppTypeRegistry>| {
                     let type_registry = type_registry.read();
@@ -82,56 +158,152 @@ pub fn compute_memos(
 
                         // call the copy_data method via reflection
                         // this will append the source data to the args tuple
-                        // FIXME indicate an error if the args don't line up?
                         if let Some(mut source) = world.get_entity_mut(*source) {
                             // insert arcane wizardry here
-                            run_as_observable(
-                                &mut source,
-                                Some(&mut args),
-                                Some(&computed),
-                                component_id,
-                                &type_id,
-                                &type_registry,
-                                Box::new(|observable, args, target| {
-                                    observable.copy_data(*target.unwrap(), args.unwrap());
-                                    None
-                                })
-                            );
+                            if
+                                let Err(error) = run_as_observable(
+                                    &mut source,
+                                    Some(&mut args),
+                                    Some(&computed),
+                                    component_id,
+                                    &type_id,
+                                    &type_registry,
+                                    Box::new(|observable, args, target| {
+                                        observable.copy_data(*target.unwrap(), args.unwrap());
+                                        None
+                                    })
+                                )
+                            {
+                                signals.record_error(computed, error);
+                            }
                         }
 
                         // make sure computeds refresh so they will be notified next time
-                        subscribe(&computed, source, &type_registry, world);
+                        if let Err(error) = subscribe(&computed, source, &type_registry, world) {
+                            signals.record_error(computed, error);
+                        }
                     }
 
                     // actually compute the computed
-                    let world = world.as_unsafe_world_cell();
-                    if let Some(handle) = world.get_entity(computed) {
-                        // safety (from the docs):
-                        // -the UnsafeEntityCell has permission to access the component mutably
-                        // -no other references to the component exist at the same time
-                        unsafe {
-                            let computed_immutable = handle.get_mut::<ComputedImmutable>().unwrap();
-
-                            // I think this world must not be used to mutate the computed, not sure
+
+                    // drop the UnsafeWorldCell after this block so we can access the real world
+                    let mut ran = false;
+                    {
+                        let world = world.as_unsafe_world_cell();
+                        if let Some(handle) = world.get_entity(computed) {
+                            // safety (from the docs):
+                            // -the UnsafeEntityCell has permission to access the component mutably
+                            // -no other references to the component exist at the same time
+                            unsafe {
+                                let computed_immutable = handle
+                                    .get_mut::<ComputedImmutable>()
+                                    .unwrap();
+
+                                // I think this world must not be used to mutate the computed,
+                                // not sure
+                                if
+                                    computed_immutable.function
+                                        .lock()
+                                        .unwrap()(&args, &computed, world.world_mut())
+                                {
+                                    // add to the changed set if the value actually changed
+                                    // (seems ok to update the LazySignalsState on the same entity)
+                                    signals.changed.insert(computed, ());
+                                }
+                            }
+                            ran = true;
+                        }
+                    }
+
+                    if ran {
+                        // keep track of any error the computed returned
+                        let component_id = component_id_set.get(computed).unwrap();
+                        let type_id = component_info_set
+                            .get(*component_id)
+                            .unwrap()
+                            .type_id()
+                            .unwrap();
+                        let mut handle = world.entity_mut(computed);
+                        match get_error(&mut handle, component_id, &type_id, &type_registry) {
+                            Ok(Some(error)) | Err(error) => {
+                                error!("ERROR running computed {:?}: {}", computed, error);
+                                signals.record_error(computed, error);
+                            }
+                            Ok(None) => {}
+                        }
+
+                        // a tracked computed now depends on whatever it read this time
+                        let mut stale = Vec::<Entity>::new();
+                        if let Some(tracked) = world.get::<TrackedSources>(computed) {
+                            let read = tracked.take();
+
+                            // refuse it if what it read this time would make it depend on itself
+                            if let Some(path) = find_cycle(&computed, &read, world) {
+                                error!("Refusing {:?}, cycle detected: {:?}", computed, path);
+                                signals.record_error(computed, LazySignalsError::Cycle(path));
+                                for source in sources.iter() {
+                                    if
+                                        let Err(error) = unsubscribe(
+                                            &computed,
+                                            source,
+                                            &type_registry,
+                                            world
+                                        )
+                                    {
+                                        signals.record_error(computed, error);
+                                    }
+                                }
+                                world
+                                    .entity_mut(computed)
+                                    .remove::<(ComputedImmutable, LazyEffect, InitDependencies)>();
+                                processed.insert(computed, ());
+                                signals.dirty.remove(computed);
+                                retracked = Some(Vec::new());
+                                return;
+                            }
+
                             if
-                                computed_immutable.function
-                                    .lock()
-                                    .unwrap()(&args, &computed, world.world_mut())
+                                let Err(error) = retrack(
+                                    &computed,
+                                    sources,
+                                    &read,
+                                    &type_registry,
+                                    world
+                                )
                             {
-                                // add to the changed set if the value actually changed
-                                // (seems ok to update the LazySignalsState on the same entity)
-                                signals.changed.insert(computed, ());
+                                signals.record_error(computed, error);
                             }
+
+                            // a newly read source that is still dirty was read before it was
+                            // recomputed, so run again once it is up to date
+                            stale = read
+                                .iter()
+                                .filter(|source| signals.dirty.contains(**source))
+                                .copied()
+                                .collect();
+                            if let Some(mut handle) = world.get_mut::<ComputedImmutable>(computed) {
+                                handle.sources = read.clone();
+                            }
+                            retracked = Some(read);
                         }
 
-                        // add the computed entity to the processed set
-                        processed.insert(computed, ());
+                        if stale.is_empty() {
+                            // add the computed entity to the processed set
+                            processed.insert(computed, ());
 
-                        // mark the computed not dirty
-                        signals.dirty.remove(computed);
+                            // mark the computed not dirty
+                            signals.dirty.remove(computed);
+                        } else {
+                            stack.push(computed);
+                            stack.append(&mut stale);
+                        }
                     }
                 });
             }
         });
+
+        if let Some(read) = retracked {
+            sources.insert(computed, read);
+        }
     }
 }
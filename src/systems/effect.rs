@@ -3,15 +3,22 @@ use bevy::{
     prelude::*,
     reflect::DynamicTuple,
     tasks::{ block_on, futures_lite::future, Task },
+    utils::Instant,
 };
 
 use crate::{ arcane_wizardry::*, framework::*, LazySignalsResource };
 
-type DeferredEffectsParam = (With<DeferredEffect>, Without<RunningTask>);
+type DeferredEffectsData = (
+    Entity,
+    &'static LazyEffect,
+    Has<RunningTask>,
+    Has<TriggeredEffect>,
+    Option<&'static TaskRetriggerPolicy>,
+);
 
 pub fn apply_deferred_effects(
     world: &mut World,
-    query_effects: &mut QueryState<(Entity, &LazyEffect), DeferredEffectsParam>
+    query_effects: &mut QueryState<DeferredEffectsData, With<DeferredEffect>>
 ) {
     trace!("EFFECTS");
 
@@ -20,9 +27,35 @@ pub fn apply_deferred_effects(
 
     // collapse the query or get world concurrency errors
     let mut relationships = EntityRelationshipSet::new();
-    world.resource_scope(|world, signals: Mut<LazySignalsResource>| {
-        query_effects.iter(world).for_each(|(entity, effect)| {
-            // only add the effect if it isn't already running
+
+    // effects that must run regardless of the changed set (queued while their task was running)
+    let mut forced = empty_set();
+
+    // tasks that are still running but want to run again once they exit
+    let mut queued = empty_set();
+
+    // effects may use the registry themselves (e.g. a command flushed from inside one), so it is
+    // not scoped out, and the lock is not held while they run
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+
+    world.resource_scope(|world, mut signals: Mut<LazySignalsResource>| {
+        query_effects.iter(world).for_each(|(entity, effect, running, triggered, policy)| {
+            let policy = policy.copied().unwrap_or_default();
+            match (running, policy) {
+                // only add the effect if it isn't already running
+                (true, TaskRetriggerPolicy::Drop) => {
+                    return;
+                }
+                (true, TaskRetriggerPolicy::QueueLatest) => {
+                    queued.insert(entity, ());
+                }
+                // a new RunningTask replaces (and cancels) the old one
+                _ => {}
+            }
+            if triggered {
+                forced.insert(entity, ());
+            }
+
             let mut deps = Vec::<Entity>::new();
             deps.append(&mut effect.sources.clone());
             deps.append(&mut effect.triggers.clone());
@@ -41,7 +74,7 @@ pub fn apply_deferred_effects(
             // only run an effect if at least one of its sources is in the changed set
             // OR it has been explicitly triggered
             let mut actually_run = false;
-            if signals.triggered.contains(effect) {
+            if signals.triggered.contains(effect) || forced.contains(effect) {
                 trace!("-triggering effect {:#?}", effect);
                 actually_run = true;
             } else {
@@ -53,8 +86,13 @@ pub fn apply_deferred_effects(
                     }
                 }
             }
-            if actually_run {
+            if actually_run && queued.contains(effect) {
+                // check_tasks schedules it again when the running task exits
+                trace!("-queued task {:#?}", effect);
+                world.entity_mut(effect).insert(TriggeredEffect);
+            } else if actually_run {
                 effects.insert(effect, ());
+                world.entity_mut(effect).remove::<TriggeredEffect>();
             }
 
             // remove the DeferredEffect component
@@ -62,12 +100,12 @@ pub fn apply_deferred_effects(
 
             // make sure if effects are deferred but not run that they still refresh
             // otherwise they will not be notified next time
-            world.resource_scope(|world, type_registry: Mut<AppTypeRegistry>| {
-                let type_registry = type_registry.read();
-                for source in sources {
-                    subscribe(&effect, source, &type_registry, world);
+            let type_registry = type_registry.read();
+            for source in sources {
+                if let Err(error) = subscribe(&effect, source, &type_registry, world) {
+                    signals.record_error(effect, error);
                 }
-            });
+            }
         }
 
         // write
@@ -80,8 +118,24 @@ pub fn apply_deferred_effects(
             let mut component_info_set = ComponentInfoSet::new();
 
             // build component id -> info map
+            let tracked = world.get::<TrackedSources>(effect).is_some();
+            let mut missing = None;
+            let mut found = Vec::<Entity>::new();
             for source in sources.iter() {
-                let immutable = world.entity(*source).get::<ImmutableState>().unwrap();
+                // tolerate sources that were destroyed or despawned by other code
+                // (including by an effect that already ran during this loop)
+                let Some(immutable) = world
+                    .get_entity(*source)
+                    .and_then(|source| source.get::<ImmutableState>()) else {
+                    // a tracked effect just runs without it and stops depending on it
+                    if tracked {
+                        trace!("-dropping missing source {:?} of {:?}", source, effect);
+                        continue;
+                    }
+                    missing = Some(*source);
+                    break;
+                };
+                found.push(*source);
                 let component_id = immutable.component_id;
                 trace!("-found an effect source with component ID {:#?}", component_id);
                 component_id_set.insert(*source, component_id);
@@ -90,93 +144,160 @@ pub fn apply_deferred_effects(
                 }
             }
 
-            world.resource_scope(|world, type_registry: Mut<AppTypeRegistry>| {
-                let type_registry = type_registry.read();
+            // don't run with a missing source, the args would not line up anyway
+            if let Some(source) = missing {
+                error!("Not running effect {:?}, source {:?} is missing", effect, source);
+                signals.record_error(effect, LazySignalsError::MissingSource(source));
+                continue;
+            }
+            let sources = found;
 
-                // prepare the args
-                let mut args = DynamicTuple::default();
+            // prepare the args
+            let mut args = DynamicTuple::default();
+            {
+                let type_registry = type_registry.read();
                 for source in sources.iter() {
                     let component_id = component_id_set.get(*source).unwrap();
                     let type_id = component_info_set.get(*component_id).unwrap().type_id().unwrap();
 
                     // call the copy_data method via reflection
                     // this will append the source data to the args tuple
-                    // FIXME indicate an error if the args don't line up?
                     if let Some(mut source) = world.get_entity_mut(*source) {
                         // insert arcane wizardry here
-                        run_as_observable(
-                            &mut source,
-                            Some(&mut args),
-                            Some(&effect),
-                            component_id,
-                            &type_id,
-                            &type_registry,
-                            Box::new(|observable, args, target| {
-                                observable.copy_data(*target.unwrap(), args.unwrap());
-                                None
-                            })
-                        );
+                        if
+                            let Err(error) = run_as_observable(
+                                &mut source,
+                                Some(&mut args),
+                                Some(&effect),
+                                component_id,
+                                &type_id,
+                                &type_registry,
+                                Box::new(|observable, args, target| {
+                                    observable.copy_data(*target.unwrap(), args.unwrap());
+                                    None
+                                })
+                            )
+                        {
+                            signals.record_error(effect, error);
+                        }
                     }
                 }
+            }
 
-                // actually run the effect
-                let mut new_task = false;
-
-                // drop the UnsafeWorldCell after this block so we can access the real world again
-                {
-                    let world = world.as_unsafe_world_cell();
-                    if let Some(handle) = world.get_entity(effect) {
-                        // safety (from the docs):
-                        // -the UnsafeEntityCell has permission to access the component mutably
-                        // -no other references to the component exist at the same time
-                        unsafe {
-                            let lazy_effect = handle.get::<LazyEffect>().unwrap();
-                            let function = &lazy_effect.function;
-                            match function {
-                                EffectContext::Short(effect) => {
-                                    // I think this world must not be used to mutate the effect, not sure
-                                    effect.lock().unwrap()(&args, world.world_mut());
-                                }
-                                EffectContext::Long(_) => {
-                                    trace!("Running task {:?}", effect);
-                                    new_task = true;
+            // actually run the effect
+            let mut new_task = false;
+
+            // drop the UnsafeWorldCell after this block so we can access the real world again
+            {
+                let world = world.as_unsafe_world_cell();
+                if let Some(handle) = world.get_entity(effect) {
+                    // safety (from the docs):
+                    // -the UnsafeEntityCell has permission to access the component mutably
+                    // -no other references to the component exist at the same time
+                    unsafe {
+                        let lazy_effect = handle.get::<LazyEffect>().unwrap();
+                        let function = &lazy_effect.function;
+                        match function {
+                            EffectContext::Short(function) => {
+                                // I think this world must not be used to mutate the effect, not sure
+                                if
+                                    let Err(error) = function.lock().unwrap()(
+                                        &args,
+                                        world.world_mut()
+                                    )
+                                {
+                                    error!("ERROR running effect {:?}: {}", effect, error);
+                                    signals.record_error(effect, error);
                                 }
                             }
+                            EffectContext::Long(_) => {
+                                trace!("Running task {:?}", effect);
+                                new_task = true;
+                            }
                         }
                     }
+                }
 
-                    // run and mark the new task
-                    if new_task {
-                        let handle = world.get_entity(effect).unwrap();
-                        unsafe {
-                            let lazy_effect = handle.get::<LazyEffect>().unwrap();
-                            let function = &lazy_effect.function;
-                            if let EffectContext::Long(function) = function {
-                                let task = function.lock().unwrap()(&args);
-                                new_tasks.push((effect, task));
+                // run and mark the new task
+                if new_task {
+                    let handle = world.get_entity(effect).unwrap();
+                    unsafe {
+                        let lazy_effect = handle.get::<LazyEffect>().unwrap();
+                        let function = &lazy_effect.function;
+                        if let EffectContext::Long(function) = function {
+                            match function.lock().unwrap()(&args) {
+                                Ok(task) => new_tasks.push((effect, task)),
+                                Err(error) => {
+                                    error!("ERROR running task {:?}: {}", effect, error);
+                                    signals.record_error(effect, error);
+                                }
                             }
                         }
                     }
                 }
-            });
+            }
+
+            // a tracked effect now depends on whatever it read this time
+            let read = world.get::<TrackedSources>(effect).map(|tracked| tracked.take());
+            if let Some(read) = read {
+                let Some(lazy_effect) = world.get::<LazyEffect>(effect) else {
+                    continue;
+                };
+
+                // keep the subscription to a source that is also a trigger
+                let old = lazy_effect.sources
+                    .iter()
+                    .filter(|source| !lazy_effect.triggers.contains(source))
+                    .copied()
+                    .collect::<Vec<Entity>>();
+                let type_registry = type_registry.read();
+                if let Err(error) = retrack(&effect, &old, &read, &type_registry, world) {
+                    signals.record_error(effect, error);
+                }
+                world.get_mut::<LazyEffect>(effect).unwrap().sources = read;
+            }
         }
     });
 
     // add all the new_tasks to their entities
     for task in new_tasks.drain(0..) {
-        world.entity_mut(task.0).insert(RunningTask { task: task.1 });
+        if let Some(mut entity) = world.get_entity_mut(task.0) {
+            let deadline = entity
+                .get::<TaskTimeout>()
+                .map(|timeout| Instant::now() + timeout.duration);
+            entity.insert(RunningTask { task: task.1, deadline });
+        }
     }
 }
 
 // get all the currently running tasks
-pub fn check_tasks(mut running_tasks: Query<(Entity, &mut RunningTask)>, mut commands: Commands) {
-    for (entity, mut running) in &mut running_tasks {
+pub fn check_tasks(
+    mut running_tasks: Query<(Entity, &mut RunningTask, Has<TriggeredEffect>)>,
+    mut signals: ResMut<LazySignalsResource>,
+    mut commands: Commands
+) {
+    let now = Instant::now();
+    for (entity, mut running, queued) in &mut running_tasks {
+        let mut done = false;
         if let Some(mut commands_queue) = block_on(future::poll_once(&mut running.task)) {
             // append the returned command queue to have it execute later
             commands.append(&mut commands_queue);
+            done = true;
+        } else if running.deadline.is_some_and(|deadline| now >= deadline) {
+            // removing the RunningTask drops the Task, which cancels it
+            error!("Task {:?} timed out", entity);
+            signals.record_error(entity, LazySignalsError::TaskTimedOut(entity));
+            done = true;
+        }
 
+        if done {
             if let Some(mut entity) = commands.get_entity(entity) {
                 entity.remove::<RunningTask>();
+
+                // it was retriggered while running, so run it again with the latest args
+                if queued {
+                    entity.insert(DeferredEffect);
+                }
             }
         }
     }
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+use crate::{ framework::*, LazySignalsDeferredErrors, LazySignalsResource };
+
+/// Send an event for each error recorded since the last time this ran, including the ones that
+/// had to wait in LazySignalsDeferredErrors.
+pub fn send_error_events(
+    mut signals: ResMut<LazySignalsResource>,
+    mut deferred: ResMut<LazySignalsDeferredErrors>,
+    mut events: EventWriter<LazySignalsErrorEvent>
+) {
+    let deferred = deferred.as_mut();
+    for (entity, error) in deferred.errors.drain(..) {
+        signals.record_error(entity, error);
+    }
+    for entity in deferred.cleared.drain(..) {
+        signals.errors.remove(&entity);
+    }
+    events.send_batch(signals.pending_errors.drain(..));
+}
@@ -1,19 +1,50 @@
-use bevy::{ ecs::world::World, prelude::* };
+use bevy::{ ecs::{ storage::SparseSet, world::World }, prelude::*, reflect::TypeInfo };
 
-use crate::{ arcane_wizardry::*, framework::* };
+use crate::{ arcane_wizardry::*, framework::*, LazySignalsResource };
 
-fn process_subs(relationships: &EntityRelationshipSet, world: &mut World) {
-    world.resource_scope(|world, type_registry: Mut<AppTypeRegistry>| {
-        let type_registry = type_registry.read();
-        for (entity, subs) in relationships.iter() {
-            // loop through the sources
-            for source in subs.iter() {
-                subscribe(entity, source, &type_registry, world);
-            }
+/// Sources that get passed as args, along with the reflected type info of the args tuple.
+type ArgsInfoSet = SparseSet<Entity, (Vec<Entity>, &'static TypeInfo)>;
+
+fn process_subs(relationships: &EntityRelationshipSet, args: &ArgsInfoSet, world: &mut World) {
+    world.resource_scope(|world, mut signals: Mut<LazySignalsResource>| {
+        world.resource_scope(|world, type_registry: Mut<AppTypeRegistry>| {
+            let type_registry = type_registry.read();
+            for (entity, subs) in relationships.iter() {
+                // refuse anything that would end up depending on itself
+                if let Some(path) = find_cycle(entity, subs, world) {
+                    error!("Refusing {:?}, cycle detected: {:?}", entity, path);
+                    signals.record_error(*entity, LazySignalsError::Cycle(path));
+                    world
+                        .get_entity_mut(*entity)
+                        .unwrap()
+                        .remove::<(ComputedImmutable, LazyEffect, InitDependencies)>();
+                    continue;
+                }
+
+                // refuse anything whose args tuple doesn't line up with its sources
+                if let Some((sources, args_info)) = args.get(*entity) {
+                    if let Err(error) = check_args(sources, args_info, world) {
+                        error!("Refusing {:?}: {}", entity, error);
+                        signals.record_error(*entity, error);
+                        world
+                            .get_entity_mut(*entity)
+                            .unwrap()
+                            .remove::<(ComputedImmutable, LazyEffect, InitDependencies)>();
+                        continue;
+                    }
+                }
 
-            // mark as processed
-            world.get_entity_mut(*entity).unwrap().remove::<InitDependencies>();
-        }
+                // loop through the sources
+                for source in subs.iter() {
+                    if let Err(error) = subscribe(entity, source, &type_registry, world) {
+                        signals.record_error(*entity, error);
+                    }
+                }
+
+                // mark as processed
+                world.get_entity_mut(*entity).unwrap().remove::<InitDependencies>();
+            }
+        });
     });
 }
 
@@ -23,6 +54,7 @@ pub fn init_effects(
     query_effects: &mut QueryState<(Entity, &LazyEffect), With<InitDependencies>>
 ) {
     let mut relationships = EntityRelationshipSet::new();
+    let mut args = ArgsInfoSet::new();
 
     // run the subscribe method on all LazyEffect.sources and .triggers
     query_effects.iter(world).for_each(|(entity, effect)| {
@@ -30,9 +62,12 @@ pub fn init_effects(
         subs.append(&mut effect.sources.clone());
         subs.append(&mut effect.triggers.clone());
         relationships.insert(entity, subs);
+
+        // only the sources are passed as args
+        args.insert(entity, (effect.sources.clone(), effect.args_info));
     });
 
-    process_subs(&relationships, world)
+    process_subs(&relationships, &args, world)
 }
 
 // FIXME should we actually just compute everything that is marked instead of faking it?
@@ -41,13 +76,15 @@ pub fn init_computeds(
     query_computeds: &mut QueryState<(Entity, &ComputedImmutable), With<InitDependencies>>
 ) {
     let mut relationships = EntityRelationshipSet::new();
+    let mut args = ArgsInfoSet::new();
 
     // run the subscribe method on all ComputedImmutable.sources
     query_computeds.iter(world).for_each(|(entity, computed)| {
         let mut subs = Vec::<Entity>::new();
         subs.append(&mut computed.sources.clone());
         relationships.insert(entity, subs);
+        args.insert(entity, (computed.sources.clone(), computed.args_info));
     });
 
-    process_subs(&relationships, world)
+    process_subs(&relationships, &args, world)
 }
@@ -1,5 +1,6 @@
 /// These are the reference user API systems, patterned after the TC39 proposal.
 pub mod computed;
 pub mod effect;
+pub mod error;
 pub mod init;
 pub mod signal;
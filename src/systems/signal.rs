@@ -1,6 +1,14 @@
-use bevy::{ ecs::world::World, prelude::* };
+use std::sync::mpsc::TryRecvError;
 
-use crate::{ arcane_wizardry::*, framework::*, LazySignalsResource };
+use bevy::{ core::FrameCount, ecs::world::World, prelude::* };
+
+use crate::{
+    arcane_wizardry::*,
+    commands::LazySignalsCommandsExt,
+    framework::*,
+    record_error,
+    LazySignalsResource,
+};
 
 fn add_subs_to_running(
     subs: &[Entity],
@@ -34,9 +42,59 @@ fn merge_running(running: &mut EntitySet, next_running: &mut EntitySet) -> bool
     }
 }
 
+/// Send the values pushed through each SignalSender (from streaming tasks or other threads) so
+/// they are processed along with everything else. Receivers whose senders are all gone are dropped.
+pub fn receive_signals(
+    receivers: Query<(Entity, &SignalReceiver, Has<LazyEffect>)>,
+    mut commands: Commands
+) {
+    for (entity, receiver, is_task) in &receivers {
+        if !receiver.function.lock().unwrap()(&mut commands) {
+            trace!("Dropping disconnected receiver {:?}", entity);
+            if is_task {
+                commands.entity(entity).remove::<SignalReceiver>();
+            } else {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+/// Destroy the effect of each watch whose SignalStream was dropped, since nobody is listening.
+pub fn check_watches(watches: Query<(Entity, &WatchGuard)>, mut commands: Commands) {
+    for (entity, guard) in &watches {
+        // nothing is ever sent, so a disconnect means the SignalStream was dropped
+        if let Err(TryRecvError::Disconnected) = guard.dropped.lock().unwrap().try_recv() {
+            trace!("Stream was dropped, destroying {:?}", entity);
+            commands.destroy_signal(entity, TeardownPolicy::ErrorOut);
+        }
+    }
+}
+
+/// Send the mirrored component fields whose components changed since the last run.
+/// A mirror whose target entity is gone stops mirroring (the state keeps its last value).
+pub fn mirror_component_paths(
+    world: &World,
+    mirrors: Query<(Entity, &ComponentPathMirror)>,
+    mut commands: Commands
+) {
+    for (entity, mirror) in &mirrors {
+        if let Err(error) = mirror.function.lock().unwrap()(world, &mut commands) {
+            error!("Could not mirror into {:?}: {}", entity, error);
+            if let LazySignalsError::MissingSource(_) = error {
+                commands.entity(entity).remove::<ComponentPathMirror>();
+            }
+
+            // this system only has read access to the world, so record the error later
+            commands.add(move |world: &mut World| record_error(world, entity, error));
+        }
+    }
+}
+
 pub fn send_signals(
     world: &mut World,
-    query_signals: &mut QueryState<(Entity, &ImmutableState), With<SendSignal>>
+    query_signals: &mut QueryState<(Entity, &ImmutableState), With<SendSignal>>,
+    query_memos: &mut QueryState<Entity, With<ComputeMemo>>
 ) {
     trace!("SIGNALS");
 
@@ -45,9 +103,10 @@ pub fn send_signals(
     let mut running = empty_set();
 
     // Phase One: find all the updated signals and schedule their direct subscribers to run
+    let frame = world.get_resource::<FrameCount>().map(|frame| frame.0);
     world.resource_scope(|world, mut signals: Mut<LazySignalsResource>| {
         // initialize sets
-        signals.init();
+        signals.init(frame);
 
         trace!("looking for signals");
         let mut count = 0;
@@ -74,6 +133,16 @@ pub fn send_signals(
             for (entity, component_id) in component_id_set.iter() {
                 let entity = *entity;
 
+                // a signal sent over and over in one frame is most likely a feedback loop
+                if !signals.count_send(entity) {
+                    let max_sends = signals.limits.max_sends_per_frame;
+                    error!("Dropping signal {:?}, sent more than {} times", entity, max_sends);
+                    let error = LazySignalsError::RunawaySignal(entity, max_sends);
+                    signals.record_error(entity, error);
+                    world.entity_mut(entity).remove::<SendSignal>();
+                    continue;
+                }
+
                 // here we need to access the Signal as an LazySignalsObservable
                 let component_id = *component_id;
                 let mut signal_to_send = world.entity_mut(entity);
@@ -89,15 +158,25 @@ pub fn send_signals(
 
                 // merge the next data value and return a list of subscribers to the change
                 // and whether these subscribers should be triggered too
-                let result = run_as_observable(
-                    &mut signal_to_send,
-                    None,
-                    None,
-                    &component_id,
-                    &type_id,
-                    &type_registry,
-                    Box::new(|observable, _args, _target| { observable.merge() })
-                ).unwrap();
+                let result = match
+                    run_as_observable(
+                        &mut signal_to_send,
+                        None,
+                        None,
+                        &component_id,
+                        &type_id,
+                        &type_registry,
+                        Box::new(|observable, _args, _target| { observable.merge() })
+                    )
+                {
+                    Ok(result) => result.unwrap(),
+                    Err(error) => {
+                        error!("Could not send signal {:?}: {}", entity, error);
+                        signals.record_error(entity, error);
+                        signal_to_send.remove::<SendSignal>();
+                        continue;
+                    }
+                };
 
                 let subs = result.0;
                 let changed = result.1;
@@ -115,6 +194,11 @@ pub fn send_signals(
                 signal_to_send.remove::<SendSignal>();
             }
 
+            // memos already marked for computation (e.g. a computed system that read changed data)
+            // are treated like they were just notified by a source
+            let marked = query_memos.iter(world).collect::<Vec<Entity>>();
+            add_subs_to_running(&marked, false, &mut next_running, &mut signals);
+
             // Phase Two: fire notifications up the subscriber tree
             let mut count = 0;
 
@@ -123,6 +207,18 @@ pub fn send_signals(
                 count += 1;
                 trace!("Sending signals iteration {}", count);
 
+                // stop cleanly if the subscriber tree is deeper than the budget allows
+                let max_iterations = signals.limits.max_iterations;
+                if count > max_iterations {
+                    error!("Stopped sending signals after {} iterations", max_iterations);
+                    let error = LazySignalsError::BudgetExceeded(max_iterations);
+                    for runner in running.indices() {
+                        signals.record_error(runner, error.clone());
+                    }
+                    running.clear();
+                    break;
+                }
+
                 // get an item from the running set
                 for runner in running.indices() {
                     // add the item to the processed set
@@ -153,22 +249,30 @@ pub fn send_signals(
                             );
 
                             // get a list of subscribers
-                            let subs = run_as_observable(
-                                &mut subscriber,
-                                None,
-                                None,
-                                &component_id,
-                                &type_id,
-                                &type_registry,
-                                Box::new(|observable, _args, _target| {
-                                    Some((observable.get_subscribers(), false, false))
-                                })
-                            );
+                            let subs = match
+                                run_as_observable(
+                                    &mut subscriber,
+                                    None,
+                                    None,
+                                    &component_id,
+                                    &type_id,
+                                    &type_registry,
+                                    Box::new(|observable, _args, _target| {
+                                        Some((observable.get_subscribers(), false, false))
+                                    })
+                                )
+                            {
+                                Ok(subs) => subs.unwrap().0,
+                                Err(error) => {
+                                    signals.record_error(runner, error);
+                                    continue;
+                                }
+                            };
 
                             // computed has its own subscribers, so add those to the next_running set
                             // and mark triggered if appropriate
                             add_subs_to_running(
-                                &subs.unwrap().0,
+                                &subs,
                                 signals.triggered.contains(runner),
                                 &mut next_running,
                                 &mut signals
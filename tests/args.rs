@@ -0,0 +1,84 @@
+use bevy::reflect::TypePath;
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+fn mismatch<E: TypePath, F: TypePath>(index: usize) -> Option<LazySignalsError> {
+    Some(LazySignalsError::ArgsTypeMismatch {
+        index,
+        expected: E::type_path().to_string(),
+        found: F::type_path().to_string(),
+    })
+}
+
+#[test]
+fn refuses_a_computed_with_mismatched_args() {
+    let mut app = app();
+    let (state, computed) = with_commands(&mut app, |commands| {
+        let number = LazySignals.state(1u32, commands);
+        let state = LazySignals.state(2u32, commands);
+        let computed = LazySignals.computed::<(Option<u32>, Option<bool>), u32>(
+            |args| args.0.map(Ok),
+            vec![number.entity(), state.entity()],
+            commands
+        );
+        (state, computed)
+    });
+    app.update();
+
+    assert_eq!(error(&app, computed), mismatch::<Option<bool>, Option<u32>>(1));
+    assert!(!app.world().entity(computed.entity()).contains::<ComputedImmutable>());
+
+    // sending to the source later doesn't run (and panic on) the refused computed either
+    with_commands(&mut app, |commands| LazySignals.send(state, 3, commands));
+    app.update();
+    assert_eq!(LazySignals.read(computed, app.world()), None);
+}
+
+#[test]
+fn refuses_an_effect_with_mismatched_args() {
+    let mut app = app();
+    let (flag, effect) = with_commands(&mut app, |commands| {
+        let flag = LazySignals.state(true, commands);
+        let effect = LazySignals.effect::<(Option<u32>,)>(
+            |_args, _world| panic!("should not run"),
+            vec![flag.entity()],
+            vec![],
+            commands
+        );
+        (flag, effect)
+    });
+    app.update();
+
+    assert_eq!(error(&app, effect), mismatch::<Option<u32>, Option<bool>>(0));
+    assert!(!app.world().entity(effect.entity()).contains::<LazyEffect>());
+
+    with_commands(&mut app, |commands| LazySignals.send(flag, false, commands));
+    app.update();
+}
+
+#[test]
+fn refuses_an_effect_with_a_missing_arg() {
+    let mut app = app();
+    let effect = with_commands(&mut app, |commands| {
+        LazySignals.effect::<(Option<u32>,)>(
+            |_args, _world| panic!("should not run"),
+            vec![],
+            vec![],
+            commands
+        )
+    });
+    app.update();
+
+    let expected = Some(LazySignalsError::ArgsTypeMismatch {
+        index: 0,
+        expected: Option::<u32>::type_path().to_string(),
+        found: "nothing".to_string(),
+    });
+    assert_eq!(error(&app, effect), expected);
+}
@@ -0,0 +1,106 @@
+use std::sync::{ atomic::{ AtomicBool, Ordering }, Arc };
+
+use bevy::{ prelude::*, tasks::futures_lite::future };
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Memo, Readable, Signal },
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+/// An async computed that doubles its source once the gate is opened, or fails on zero. It stays
+/// Idle until the source is sent the given value.
+fn gated(app: &mut App, first: u32) -> (Signal<u32>, Memo<AsyncValue<u32>>, Arc<AtomicBool>) {
+    let gate = Arc::new(AtomicBool::new(false));
+    let open = gate.clone();
+    let (state, memo) = with_commands(app, |commands| {
+        let state = LazySignals.state(u32::MAX, commands);
+        let memo = LazySignals.async_computed::<(Option<u32>,), u32, String, _>(
+            move |args| {
+                let open = open.clone();
+                async move {
+                    while !open.load(Ordering::SeqCst) {
+                        future::yield_now().await;
+                    }
+                    match args.0 {
+                        Some(0) => Err("zero".to_string()),
+                        value => Ok(value.unwrap_or_default() * 2),
+                    }
+                }
+            },
+            vec![state.entity()],
+            commands
+        );
+        (state, memo)
+    });
+    app.update();
+    assert_eq!(value(app, memo), Some(AsyncValue::Idle));
+
+    with_commands(app, |commands| LazySignals.send(state, first, commands));
+    (state, memo, gate)
+}
+
+fn value(app: &App, memo: Memo<AsyncValue<u32>>) -> Option<AsyncValue<u32>> {
+    LazySignals.read(memo, app.world()).and_then(Result::ok)
+}
+
+#[test]
+fn loads_then_stores_the_value() {
+    let mut app = app();
+    let (state, memo, gate) = gated(&mut app, 1);
+    assert!(update_until(&mut app, |app| value(app, memo) == Some(AsyncValue::Loading)));
+
+    gate.store(true, Ordering::SeqCst);
+    assert!(update_until(&mut app, |app| value(app, memo) == Some(AsyncValue::Ready(2))));
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 5, commands));
+    assert!(update_until(&mut app, |app| value(app, memo) == Some(AsyncValue::Ready(10))));
+}
+
+#[test]
+fn loads_then_stores_the_failure() {
+    let mut app = app();
+    let (_, memo, gate) = gated(&mut app, 0);
+    assert!(update_until(&mut app, |app| value(app, memo) == Some(AsyncValue::Loading)));
+
+    gate.store(true, Ordering::SeqCst);
+    let failed = Some(AsyncValue::Failed("zero".to_string()));
+    assert!(update_until(&mut app, |app| value(app, memo) == failed));
+}
+
+#[test]
+fn stores_cancelled_when_the_task_is_cancelled() {
+    let mut app = app();
+    let (_, memo, gate) = gated(&mut app, 1);
+    assert!(update_until(&mut app, |app| value(app, memo) == Some(AsyncValue::Loading)));
+
+    with_commands(&mut app, |commands| LazySignals.cancel_task(memo, commands));
+    let cancelled = Some(AsyncValue::Failed("cancelled".to_string()));
+    assert!(update_until(&mut app, |app| value(app, memo) == cancelled));
+    assert!(!app.world().entity(memo.entity()).contains::<RunningTask>());
+
+    // opening the gate now has nothing left to finish
+    gate.store(true, Ordering::SeqCst);
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(value(&app, memo), cancelled);
+}
+
+#[test]
+fn keeps_only_the_result_of_the_latest_run() {
+    let mut app = app();
+    let (state, memo, gate) = gated(&mut app, 1);
+    assert!(update_until(&mut app, |app| value(app, memo) == Some(AsyncValue::Loading)));
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 2, commands));
+    app.update();
+    gate.store(true, Ordering::SeqCst);
+    assert!(update_until(&mut app, |app| value(app, memo) == Some(AsyncValue::Ready(4))));
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(value(&app, memo), Some(AsyncValue::Ready(4)));
+}
@@ -0,0 +1,96 @@
+use bevy::{ ecs::component::Tick, prelude::* };
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Signal },
+    bindings::LazySignalsAppExt,
+    LazySignalsResource,
+};
+
+mod common;
+use common::*;
+
+#[derive(Resource)]
+struct Volume(f64);
+
+fn bound(volume: f64) -> (App, Signal<f64>) {
+    let mut app = app();
+    app.insert_resource(Volume(volume));
+    let signal = with_commands(&mut app, |commands| LazySignals.state(0.0, commands));
+    app.bind_resource::<Volume, f64>(
+        signal,
+        |volume| volume.0,
+        |volume, value| {
+            volume.0 = value;
+        }
+    );
+    app.update();
+    (app, signal)
+}
+
+fn read(app: &App, signal: Signal<f64>) -> f64 {
+    LazySignals.read(signal, app.world()).unwrap().unwrap()
+}
+
+fn sent(app: &App, signal: Signal<f64>) -> bool {
+    app.world().resource::<LazySignalsResource>().changed.contains(signal.into())
+}
+
+fn written(app: &App) -> Tick {
+    app.world().resource_ref::<Volume>().last_changed()
+}
+
+/// Neither side should hear back from the other on the following frames.
+fn assert_settled(app: &mut App, signal: Signal<f64>) {
+    let tick = written(app);
+    for _ in 0..3 {
+        app.update();
+        assert!(!sent(app, signal));
+        assert_eq!(written(app), tick);
+    }
+}
+
+#[test]
+fn starts_with_the_resource_value() {
+    let (mut app, signal) = bound(0.25);
+    assert_eq!(read(&app, signal), 0.25);
+    assert_settled(&mut app, signal);
+}
+
+#[test]
+fn sends_a_resource_write_to_the_signal() {
+    let (mut app, signal) = bound(0.25);
+    app.world_mut().resource_mut::<Volume>().0 = 0.5;
+    let tick = written(&app);
+    app.update();
+
+    assert!(sent(&app, signal));
+    assert_eq!(read(&app, signal), 0.5);
+    assert_eq!(written(&app), tick);
+    assert_settled(&mut app, signal);
+}
+
+#[test]
+fn writes_a_signal_commit_to_the_resource() {
+    let (mut app, signal) = bound(0.25);
+    with_commands(&mut app, |commands| LazySignals.send(signal, 0.75, commands));
+    app.update();
+
+    assert_eq!(app.world().resource::<Volume>().0, 0.75);
+    assert_settled(&mut app, signal);
+}
+
+#[test]
+fn does_not_bounce_nan_back_and_forth() {
+    let (mut app, signal) = bound(0.25);
+    app.world_mut().resource_mut::<Volume>().0 = f64::NAN;
+    app.update();
+    assert!(read(&app, signal).is_nan());
+    assert_settled(&mut app, signal);
+
+    with_commands(&mut app, |commands| LazySignals.send(signal, 1.0, commands));
+    app.update();
+    with_commands(&mut app, |commands| LazySignals.send(signal, f64::NAN, commands));
+    app.update();
+    assert!(app.world().resource::<Volume>().0.is_nan());
+    assert_settled(&mut app, signal);
+}
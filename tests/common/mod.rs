@@ -0,0 +1,51 @@
+// each test crate only uses some of these
+#![allow(dead_code)]
+
+use std::{ thread::sleep, time::Duration };
+
+use bevy::{ core::FrameCountPlugin, ecs::world::CommandQueue, prelude::* };
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Memo },
+    framework::*,
+    LazySignalsPlugin,
+    LazySignalsResource,
+};
+
+pub fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((TaskPoolPlugin::default(), FrameCountPlugin, LazySignalsPlugin));
+    app
+}
+
+/// Queue commands against the world and apply them right away.
+pub fn with_commands<R>(app: &mut App, closure: impl FnOnce(&mut Commands) -> R) -> R {
+    let mut queue = CommandQueue::default();
+    let result = closure(&mut Commands::new(&mut queue, app.world()));
+    queue.apply(app.world_mut());
+    result
+}
+
+/// Keep updating the app until the condition holds, for things that wait on other threads.
+pub fn update_until(app: &mut App, condition: impl Fn(&App) -> bool) -> bool {
+    for _ in 0..500 {
+        app.update();
+        if condition(app) {
+            return true;
+        }
+        sleep(Duration::from_millis(2));
+    }
+    false
+}
+
+pub fn error(app: &App, entity: impl Into<Entity>) -> Option<LazySignalsError> {
+    app.world().resource::<LazySignalsResource>().errors.get(&entity.into()).cloned()
+}
+
+pub fn double(commands: &mut Commands, source: Entity) -> Memo<u32> {
+    LazySignals.computed::<(Option<u32>,), u32>(
+        |args| args.0.map(|value| Ok(value * 2)),
+        vec![source],
+        commands
+    )
+}
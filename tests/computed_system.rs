@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use bevy_lazy_signals::{ api::{ LazySignals, Readable }, framework::* };
+
+mod common;
+use common::*;
+
+#[derive(Resource)]
+struct Bonus(u32);
+
+#[derive(Component)]
+struct Health(u32);
+
+fn with_bonus(In(args): In<(Option<u32>,)>, bonus: Res<Bonus>) -> LazySignalsResult<u32> {
+    args.0.map(|value| Ok(value + bonus.0))
+}
+
+fn total_health(In(_): In<()>, query: Query<&Health>) -> LazySignalsResult<u32> {
+    Some(Ok(query.iter().map(|health| health.0).sum()))
+}
+
+#[test]
+fn recomputes_when_a_resource_it_reads_changes() {
+    let mut app = app();
+    app.insert_resource(Bonus(10));
+    let (state, memo) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let memo = LazySignals.computed_system::<(Option<u32>,), u32, _, _>(
+            with_bonus,
+            vec![state.entity()],
+            commands
+        );
+        (state, memo)
+    });
+    app.update();
+    assert_eq!(LazySignals.read(memo, app.world()), Some(Ok(11)));
+
+    // no signal is sent, only the resource changes
+    app.world_mut().resource_mut::<Bonus>().0 = 20;
+    app.update();
+    assert_eq!(LazySignals.read(memo, app.world()), Some(Ok(21)));
+
+    // the source still notifies it too
+    with_commands(&mut app, |commands| LazySignals.send(state, 2, commands));
+    app.update();
+    assert_eq!(LazySignals.read(memo, app.world()), Some(Ok(22)));
+}
+
+#[test]
+fn recomputes_when_a_component_it_reads_changes() {
+    let mut app = app();
+    let hero = app.world_mut().spawn(Health(5)).id();
+    app.world_mut().spawn(Health(7));
+    let memo = with_commands(&mut app, |commands| {
+        LazySignals.computed_system::<(), u32, _, _>(total_health, vec![], commands)
+    });
+    app.update();
+    assert_eq!(LazySignals.read(memo, app.world()), Some(Ok(12)));
+
+    app.world_mut().get_mut::<Health>(hero).unwrap().0 = 1;
+    app.update();
+    assert_eq!(LazySignals.read(memo, app.world()), Some(Ok(8)));
+}
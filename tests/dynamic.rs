@@ -0,0 +1,41 @@
+use bevy::reflect::DynamicStruct;
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::*,
+    LazySignalsResource,
+};
+
+mod common;
+use common::*;
+
+fn point(x: u32, label: &str) -> LazySignalsDynamic<DynamicStruct> {
+    let mut point = DynamicStruct::default();
+    point.insert("x", x);
+    point.insert("label", label.to_string());
+    LazySignalsDynamic(point)
+}
+
+#[test]
+fn notifies_only_when_a_dynamic_struct_changes() {
+    let mut app = app();
+    let state = with_commands(&mut app, |commands| LazySignals.state(point(0, "a"), commands));
+    app.update();
+    let changed = |app: &bevy::prelude::App| {
+        app.world().resource::<LazySignalsResource>().changed.contains(state.entity())
+    };
+
+    with_commands(&mut app, |commands| LazySignals.send(state, point(1, "a"), commands));
+    app.update();
+    assert!(changed(&app));
+
+    // a different DynamicStruct with the same fields is equal
+    with_commands(&mut app, |commands| LazySignals.send(state, point(1, "a"), commands));
+    app.update();
+    assert!(!changed(&app));
+
+    with_commands(&mut app, |commands| LazySignals.send(state, point(1, "b"), commands));
+    app.update();
+    assert!(changed(&app));
+    assert_eq!(LazySignals.read(state, app.world()), Some(Ok(point(1, "b"))));
+}
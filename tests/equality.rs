@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::*,
+    LazySignalsResource,
+};
+
+mod common;
+use common::*;
+
+fn within_a_hundredth() -> EqualityStrategy<f64> {
+    EqualityStrategy::Custom(Arc::new(|a: &f64, b: &f64| (a - b).abs() < 0.01))
+}
+
+fn changed(app: &App, entity: impl Into<Entity>) -> bool {
+    app.world().resource::<LazySignalsResource>().changed.contains(entity.into())
+}
+
+#[test]
+fn a_custom_comparator_suppresses_a_sent_value() {
+    let mut app = app();
+    let state = with_commands(&mut app, |commands| {
+        LazySignals.state_with_equality(1.0, within_a_hundredth(), commands)
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 1.005, commands));
+    app.update();
+    assert!(!changed(&app, state));
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 1.5, commands));
+    app.update();
+    assert!(changed(&app, state));
+}
+
+#[test]
+fn always_notifies_on_an_equal_sent_value() {
+    let mut app = app();
+    let state = with_commands(&mut app, |commands| {
+        LazySignals.state_with_equality(1u32, EqualityStrategy::Always, commands)
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 1, commands));
+    app.update();
+    assert!(changed(&app, state));
+}
+
+#[test]
+fn a_custom_comparator_suppresses_a_recomputed_value() {
+    let mut app = app();
+    let (state, memo) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1000u32, commands);
+        let memo = LazySignals.computed_with_equality::<(Option<u32>,), f64>(
+            |args| args.0.map(|value| Ok((value as f64) / 1000.0)),
+            vec![state.entity()],
+            within_a_hundredth(),
+            commands
+        );
+        (state, memo)
+    });
+    app.update();
+    with_commands(&mut app, |commands| LazySignals.send(state, 2000, commands));
+    app.update();
+    assert!(changed(&app, memo));
+    assert_eq!(LazySignals.read(memo, app.world()), Some(Ok(2.0)));
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 2005, commands));
+    app.update();
+    assert!(changed(&app, state));
+    assert!(!changed(&app, memo));
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 3000, commands));
+    app.update();
+    assert!(changed(&app, memo));
+}
+
+#[test]
+fn always_notifies_on_an_equal_recomputed_value() {
+    let mut app = app();
+    let (state, memo) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let memo = LazySignals.computed_with_equality::<(Option<u32>,), bool>(
+            |args| args.0.map(|value| Ok(value > 0)),
+            vec![state.entity()],
+            EqualityStrategy::Always,
+            commands
+        );
+        (state, memo)
+    });
+    app.update();
+    with_commands(&mut app, |commands| LazySignals.send(state, 2, commands));
+    app.update();
+    assert_eq!(LazySignals.read(memo, app.world()), Some(Ok(true)));
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 3, commands));
+    app.update();
+    assert!(changed(&app, memo));
+}
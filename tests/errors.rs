@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+fn error_events(app: &App) -> Vec<LazySignalsErrorEvent> {
+    let events = app.world().resource::<Events<LazySignalsErrorEvent>>();
+    events.get_reader().read(events).cloned().collect()
+}
+
+#[test]
+fn sends_an_event_for_a_recorded_error() {
+    let mut app = app();
+    let (state, first, second) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let first = double(commands, state.entity());
+        let second = double(commands, first.entity());
+        (state, first, second)
+    });
+    app.update();
+    assert!(error_events(&app).is_empty());
+
+    with_commands(&mut app, |commands| {
+        LazySignals.rewire(first, vec![second.entity()], vec![], false, commands);
+        LazySignals.send(state, 2, commands);
+    });
+    app.update();
+
+    let events = error_events(&app);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].entity, first.entity());
+    assert!(matches!(events[0].error, LazySignalsError::Cycle(_)));
+}
+
+#[test]
+fn sends_an_event_for_an_error_recorded_inside_an_effect() {
+    let mut app = app();
+    let (state, first, second) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let first = double(commands, state.entity());
+        let second = double(commands, first.entity());
+        (state, first, second)
+    });
+    app.update();
+
+    // LazySignalsResource is scoped out while the effect runs, so this error has to wait
+    with_commands(&mut app, |commands| {
+        LazySignals.effect::<(Option<u32>,)>(
+            move |_args, world| {
+                LazySignals.rewire(first, vec![second.entity()], vec![], false, &mut world.commands());
+                world.flush_commands();
+            },
+            vec![state.entity()],
+            vec![],
+            commands
+        );
+    });
+    app.update();
+    with_commands(&mut app, |commands| LazySignals.send(state, 2, commands));
+    app.update();
+
+    let events = error_events(&app);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].entity, first.entity());
+    assert!(matches!(error(&app, first), Some(LazySignalsError::Cycle(_))));
+}
+
+#[test]
+fn forgets_the_error_of_a_destroyed_entity() {
+    let mut app = app();
+    let (first, second) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let first = double(commands, state.entity());
+        (first, double(commands, first.entity()))
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| {
+        LazySignals.rewire(first, vec![second.entity()], vec![], false, commands);
+    });
+    app.update();
+    assert!(error(&app, first).is_some());
+
+    with_commands(&mut app, |commands| {
+        LazySignals.destroy(first, TeardownPolicy::ErrorOut, commands);
+    });
+    app.update();
+    assert_eq!(error(&app, first), None);
+    assert_eq!(error(&app, second), Some(LazySignalsError::MissingSource(first.entity())));
+
+    // an entity that reuses the index starts clean
+    let recycled = app.world_mut().spawn_empty().id();
+    assert_eq!(recycled.index(), first.entity().index());
+    assert_eq!(error(&app, recycled), None);
+}
@@ -0,0 +1,26 @@
+use bevy_lazy_signals::{
+    api::make_computed_with,
+    commands::LazySignalsCommandsExt,
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+#[test]
+fn refuses_a_computed_that_depends_on_itself() {
+    let mut app = app();
+    let computed = with_commands(&mut app, |commands| {
+        let computed = commands.spawn_empty().id();
+        commands.create_computed::<(Option<u32>,), u32>(
+            computed,
+            make_computed_with(|args: (Option<u32>,)| args.0.map(Ok)),
+            vec![computed]
+        );
+        computed
+    });
+    app.update();
+
+    assert!(matches!(error(&app, computed), Some(LazySignalsError::Cycle(_))));
+    assert!(!app.world().entity(computed).contains::<ComputedImmutable>());
+}
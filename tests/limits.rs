@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Memo, Readable, Signal },
+    framework::*,
+    lazy_signals_flush_systems,
+    LazySignalsLimits,
+    LazySignalsResource,
+};
+
+mod common;
+use common::*;
+
+fn limits(app: &mut App) -> Mut<'_, LazySignalsLimits> {
+    app.world_mut().resource_mut::<LazySignalsResource>().map_unchanged(|signals| &mut signals.limits)
+}
+
+fn chain(app: &mut App, length: usize) -> (Signal<u32>, Vec<Memo<u32>>) {
+    with_commands(app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let mut memos = vec![double(commands, state.entity())];
+        for _ in 1..length {
+            let memo = double(commands, memos.last().unwrap().entity());
+            memos.push(memo);
+        }
+        (state, memos)
+    })
+}
+
+#[test]
+fn stops_sending_past_max_iterations() {
+    let mut app = app();
+    let (state, memos) = chain(&mut app, 4);
+    app.update();
+    limits(&mut app).max_iterations = 2;
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 2, commands));
+    app.update();
+
+    assert_eq!(error(&app, memos[2]), Some(LazySignalsError::BudgetExceeded(2)));
+    assert_eq!(LazySignals.read(memos[0], app.world()), Some(Ok(4)));
+}
+
+#[test]
+fn stops_computing_past_max_nodes() {
+    let mut app = app();
+    let (state, memos) = chain(&mut app, 3);
+    app.update();
+    limits(&mut app).max_nodes = 1;
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 2, commands));
+    app.update();
+
+    let exceeded = Some(LazySignalsError::BudgetExceeded(1));
+    assert!(memos.iter().any(|memo| error(&app, *memo) == exceeded));
+}
+
+#[test]
+fn drops_a_signal_sent_past_max_sends_per_frame() {
+    #[derive(Resource)]
+    struct Resend(Signal<u32>);
+
+    let mut app = app();
+    let (state, _) = chain(&mut app, 1);
+    app.update();
+    limits(&mut app).max_sends_per_frame = 1;
+
+    // one send in each of two frames is fine
+    for value in 2..4 {
+        with_commands(&mut app, |commands| LazySignals.send(state, value, commands));
+        app.update();
+    }
+    assert_eq!(error(&app, state), None);
+
+    // a second send in the same frame (flushed in Update) is not
+    app.insert_resource(Resend(state)).add_systems(
+        Update,
+        (
+            |resend: Res<Resend>, mut commands: Commands| {
+                LazySignals.send(resend.0, 10, &mut commands);
+            },
+            lazy_signals_flush_systems(),
+        ).chain()
+    );
+    with_commands(&mut app, |commands| LazySignals.send(state, 5, commands));
+    app.update();
+
+    assert_eq!(error(&app, state), Some(LazySignalsError::RunawaySignal(state.entity(), 1)));
+    assert_eq!(LazySignals.read(state, app.world()), Some(Ok(5)));
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::{ lazy_immutable::LazySignalsState, * },
+    LazySignalsResource,
+};
+
+mod common;
+use common::*;
+
+#[derive(Clone, Debug, PartialEq, Reflect)]
+struct Stats {
+    health: f32,
+    name: String,
+}
+
+#[test]
+fn projects_a_field_of_a_struct_signal() {
+    let mut app = app();
+    app.register_type::<LazySignalsState<Stats>>().register_type::<LazySignalsState<f32>>();
+    let stats = Stats { health: 10.0, name: "hero".to_string() };
+    let (state, health, missing) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(Stats { health: 0.0, ..stats.clone() }, commands);
+        let health = LazySignals.project::<Stats, f32>(state, "health", commands);
+        let missing = LazySignals.project::<Stats, f32>(state, "mana", commands);
+        (state, health, missing)
+    });
+    app.update();
+    with_commands(&mut app, |commands| LazySignals.send(state, stats.clone(), commands));
+    app.update();
+    assert_eq!(LazySignals.read(health, app.world()), Some(Ok(10.0)));
+    let invalid = LazySignalsError::InvalidPath("mana".to_string());
+    assert_eq!(LazySignals.read(missing, app.world()), Some(Err(invalid)));
+
+    let changed = |app: &App| {
+        app.world().resource::<LazySignalsResource>().changed.contains(health.entity())
+    };
+
+    // another field changing leaves the projection alone
+    let renamed = Stats { name: "villain".to_string(), ..stats };
+    with_commands(&mut app, |commands| LazySignals.send(state, renamed.clone(), commands));
+    app.update();
+    assert!(!changed(&app));
+
+    let hurt = Stats { health: 4.5, ..renamed };
+    with_commands(&mut app, |commands| LazySignals.send(state, hurt, commands));
+    app.update();
+    assert!(changed(&app));
+    assert_eq!(LazySignals.read(health, app.world()), Some(Ok(4.5)));
+}
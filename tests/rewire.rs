@@ -0,0 +1,93 @@
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+#[test]
+fn refuses_a_rewire_into_a_cycle_and_keeps_the_old_sources() {
+    let mut app = app();
+    let (state, first, second) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let first = double(commands, state.entity());
+        let second = double(commands, first.entity());
+        (state, first, second)
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| {
+        LazySignals.rewire(first, vec![second.entity()], vec![], false, commands);
+        LazySignals.send(state, 2, commands);
+    });
+    app.update();
+
+    assert!(matches!(error(&app, first), Some(LazySignalsError::Cycle(_))));
+    assert_eq!(LazySignals.read(first, app.world()), Some(Ok(4)));
+    assert_eq!(LazySignals.read(second, app.world()), Some(Ok(8)));
+}
+
+#[test]
+fn refuses_a_rewire_to_a_mismatched_source_and_keeps_the_old_sources() {
+    let mut app = app();
+    let (state, flag, computed) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let flag = LazySignals.state(true, commands);
+        (state, flag, double(commands, state.entity()))
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| {
+        LazySignals.rewire(computed, vec![flag.entity()], vec![], false, commands);
+    });
+    app.update();
+
+    assert!(matches!(error(&app, computed), Some(LazySignalsError::ArgsTypeMismatch { .. })));
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 3, commands));
+    app.update();
+
+    assert!(app.world().entity(computed.entity()).contains::<ComputedImmutable>());
+    assert_eq!(LazySignals.read(computed, app.world()), Some(Ok(6)));
+}
+
+#[test]
+fn rewires_from_inside_an_effect() {
+    let mut app = app();
+    let (trigger, first, second, computed) = with_commands(&mut app, |commands| {
+        let trigger = LazySignals.state(1u32, commands);
+        let first = LazySignals.state(1u32, commands);
+        let second = LazySignals.state(10u32, commands);
+        (trigger, first, second, double(commands, first.entity()))
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| {
+        LazySignals.effect::<(Option<u32>,)>(
+            move |_args, world| {
+                LazySignals.rewire(computed, vec![second.entity()], vec![], true, &mut world.commands());
+                world.flush_commands();
+            },
+            vec![trigger.entity()],
+            vec![],
+            commands
+        );
+    });
+    app.update();
+    with_commands(&mut app, |commands| LazySignals.send(trigger, 2, commands));
+    app.update();
+    app.update();
+
+    assert_eq!(error(&app, computed), None);
+    assert_eq!(LazySignals.read(computed, app.world()), Some(Ok(20)));
+
+    // only the new source notifies it now
+    with_commands(&mut app, |commands| LazySignals.send(first, 2, commands));
+    app.update();
+    assert_eq!(LazySignals.read(computed, app.world()), Some(Ok(20)));
+
+    with_commands(&mut app, |commands| LazySignals.send(second, 30, commands));
+    app.update();
+    assert_eq!(LazySignals.read(computed, app.world()), Some(Ok(60)));
+}
@@ -0,0 +1,167 @@
+use std::{
+    sync::{ atomic::{ AtomicBool, AtomicUsize, Ordering }, Arc, Mutex },
+    thread::sleep,
+    time::Duration,
+};
+
+use bevy::{
+    ecs::world::CommandQueue,
+    prelude::*,
+    tasks::{ futures_lite::future, AsyncComputeTaskPool },
+};
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable, Signal, TaskHandle },
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+/// Bumps the count when the task future is dropped before it finishes.
+struct CancelGuard(Arc<AtomicUsize>, bool);
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        if !self.1 {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// A task that remembers each value it was started with and waits until it is let go.
+#[derive(Clone, Default)]
+struct Probe {
+    started: Arc<Mutex<Vec<u32>>>,
+    cancelled: Arc<AtomicUsize>,
+    open: Arc<AtomicBool>,
+}
+
+impl Probe {
+    fn spawn(&self, state: Signal<u32>, options: TaskOptions, app: &mut App) -> TaskHandle {
+        let probe = self.clone();
+        with_commands(app, |commands| {
+            LazySignals.task_with::<(Option<u32>,)>(
+                move |args| {
+                    let probe = probe.clone();
+                    AsyncComputeTaskPool::get().spawn(async move {
+                        probe.started.lock().unwrap().push(args.0.unwrap());
+                        let mut guard = CancelGuard(probe.cancelled.clone(), false);
+                        while !probe.open.load(Ordering::SeqCst) {
+                            future::yield_now().await;
+                        }
+                        guard.1 = true;
+                        CommandQueue::default()
+                    })
+                },
+                vec![state.entity()],
+                vec![],
+                options,
+                commands
+            )
+        })
+    }
+
+    fn started(&self) -> Vec<u32> {
+        self.started.lock().unwrap().clone()
+    }
+
+    fn cancelled(&self) -> usize {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn open(&self) {
+        self.open.store(true, Ordering::SeqCst);
+    }
+}
+
+fn running(app: &App, task: TaskHandle) -> bool {
+    app.world().entity(task.entity()).contains::<RunningTask>()
+}
+
+fn setup(options: TaskOptions) -> (App, Probe, Signal<u32>, TaskHandle) {
+    let mut app = app();
+    let probe = Probe::default();
+    let state = with_commands(&mut app, |commands| LazySignals.state(0u32, commands));
+    let task = probe.spawn(state, options, &mut app);
+    app.update();
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 1, commands));
+    assert!(update_until(&mut app, |app| running(app, task) && probe.started() == vec![1]));
+    (app, probe, state, task)
+}
+
+#[test]
+fn drops_a_trigger_while_running() {
+    let (mut app, probe, state, task) = setup(TaskOptions {
+        policy: TaskRetriggerPolicy::Drop,
+        ..default()
+    });
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 2, commands));
+    app.update();
+    assert!(running(&app, task));
+
+    probe.open();
+    assert!(update_until(&mut app, |app| !running(app, task)));
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(probe.started(), vec![1]);
+    assert_eq!(probe.cancelled(), 0);
+}
+
+#[test]
+fn runs_once_more_with_the_latest_args_after_exiting() {
+    let (mut app, probe, state, task) = setup(TaskOptions {
+        policy: TaskRetriggerPolicy::QueueLatest,
+        ..default()
+    });
+
+    for value in 2..4 {
+        with_commands(&mut app, |commands| LazySignals.send(state, value, commands));
+        app.update();
+    }
+    assert_eq!(probe.started(), vec![1]);
+
+    probe.open();
+    assert!(update_until(&mut app, |app| !running(app, task) && probe.started().len() == 2));
+    for _ in 0..10 {
+        app.update();
+    }
+    assert_eq!(probe.started(), vec![1, 3]);
+    assert_eq!(probe.cancelled(), 0);
+}
+
+#[test]
+fn cancels_and_restarts_on_a_trigger_while_running() {
+    let (mut app, probe, state, task) = setup(TaskOptions {
+        policy: TaskRetriggerPolicy::CancelAndRestart,
+        ..default()
+    });
+
+    with_commands(&mut app, |commands| LazySignals.send(state, 2, commands));
+    assert!(update_until(&mut app, |_| probe.started() == vec![1, 2] && probe.cancelled() == 1));
+    assert!(running(&app, task));
+
+    probe.open();
+    assert!(update_until(&mut app, |app| !running(app, task)));
+    assert_eq!(probe.started(), vec![1, 2]);
+    assert_eq!(probe.cancelled(), 1);
+}
+
+#[test]
+fn drops_a_task_that_times_out() {
+    let (mut app, probe, _, task) = setup(TaskOptions {
+        timeout: Some(Duration::from_millis(200)),
+        ..default()
+    });
+    assert_eq!(error(&app, task), None);
+
+    sleep(Duration::from_millis(250));
+    app.update();
+
+    assert_eq!(error(&app, task), Some(LazySignalsError::TaskTimedOut(task.entity())));
+    assert!(!running(&app, task));
+    assert!(update_until(&mut app, |_| probe.cancelled() == 1));
+}
@@ -0,0 +1,96 @@
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+#[test]
+fn errors_out_dependents_of_a_destroyed_signal() {
+    let mut app = app();
+    let (state, computed) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        (state, double(commands, state.entity()))
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| {
+        LazySignals.destroy(state, TeardownPolicy::ErrorOut, commands);
+    });
+    app.update();
+
+    let missing = LazySignalsError::MissingSource(state.entity());
+    assert!(app.world().get_entity(state.entity()).is_none());
+    assert_eq!(LazySignals.read(computed, app.world()), Some(Err(missing.clone())));
+    assert_eq!(error(&app, computed), Some(missing));
+}
+
+#[test]
+fn cascades_to_dependents_of_a_destroyed_signal() {
+    let mut app = app();
+    let (state, first, second) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        let first = double(commands, state.entity());
+        let second = double(commands, first.entity());
+        (state, first, second)
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| {
+        LazySignals.destroy(state, TeardownPolicy::Cascade, commands);
+    });
+    app.update();
+
+    assert!(app.world().get_entity(first.entity()).is_none());
+    assert!(app.world().get_entity(second.entity()).is_none());
+}
+
+#[test]
+fn destroys_from_inside_an_effect() {
+    let mut app = app();
+    let (trigger, state, first, second) = with_commands(&mut app, |commands| {
+        let trigger = LazySignals.state(1u32, commands);
+        let state = LazySignals.state(1u32, commands);
+        let first = double(commands, state.entity());
+        let second = double(commands, first.entity());
+        (trigger, state, first, second)
+    });
+    app.update();
+
+    // leave an error on the computed that gets destroyed
+    with_commands(&mut app, |commands| {
+        LazySignals.rewire(first, vec![second.entity()], vec![], false, commands);
+    });
+    app.update();
+    assert!(error(&app, first).is_some());
+
+    with_commands(&mut app, |commands| {
+        LazySignals.effect::<(Option<u32>,)>(
+            move |_args, world| {
+                LazySignals.destroy(first, TeardownPolicy::ErrorOut, &mut world.commands());
+                world.flush_commands();
+            },
+            vec![trigger.entity()],
+            vec![],
+            commands
+        );
+    });
+    app.update();
+    with_commands(&mut app, |commands| LazySignals.send(trigger, 2, commands));
+    app.update();
+
+    // the error sent to the dependent is merged the next time the systems run
+    app.update();
+
+    let missing = LazySignalsError::MissingSource(first.entity());
+    assert!(app.world().get_entity(first.entity()).is_none());
+    assert_eq!(error(&app, first), None);
+    assert_eq!(error(&app, second), Some(missing.clone()));
+    assert_eq!(LazySignals.read(second, app.world()), Some(Err(missing)));
+
+    // the source it was subscribed to still works
+    with_commands(&mut app, |commands| LazySignals.send(state, 5, commands));
+    app.update();
+    assert_eq!(LazySignals.read(state, app.world()), Some(Ok(5)));
+}
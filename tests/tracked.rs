@@ -0,0 +1,35 @@
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+#[test]
+fn retracks_a_tracked_computed_away_from_a_destroyed_source() {
+    let mut app = app();
+    let (gone, kept, computed) = with_commands(&mut app, |commands| {
+        let gone = LazySignals.state(1u32, commands);
+        let kept = LazySignals.state(10u32, commands);
+        let computed = LazySignals.tracked_computed(
+            move |cx| Some(Ok(cx.get(gone).unwrap_or(0) + cx.get(kept)?)),
+            commands
+        );
+        (gone, kept, computed)
+    });
+    app.update();
+    assert_eq!(LazySignals.read(computed, app.world()), Some(Ok(11)));
+
+    with_commands(&mut app, |commands| {
+        LazySignals.destroy(gone, TeardownPolicy::ErrorOut, commands);
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| LazySignals.send(kept, 20, commands));
+    app.update();
+
+    assert_eq!(LazySignals.read(computed, app.world()), Some(Ok(20)));
+    let tracked = app.world().entity(computed.entity()).get::<ComputedImmutable>().unwrap();
+    assert_eq!(tracked.sources, vec![kept.entity()]);
+}
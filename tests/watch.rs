@@ -0,0 +1,28 @@
+use bevy::tasks::{ block_on, futures_lite::{ future, StreamExt } };
+
+use bevy_lazy_signals::{
+    api::{ LazySignals, Readable },
+    framework::*,
+};
+
+mod common;
+use common::*;
+
+#[test]
+fn ends_a_watch_when_the_watched_signal_is_destroyed() {
+    let mut app = app();
+    let (state, mut stream) = with_commands(&mut app, |commands| {
+        let state = LazySignals.state(1u32, commands);
+        (state, LazySignals.watch(state, commands))
+    });
+    app.update();
+
+    with_commands(&mut app, |commands| {
+        LazySignals.destroy(state, TeardownPolicy::ErrorOut, commands);
+    });
+    app.update();
+
+    let missing = LazySignalsError::MissingSource(state.entity());
+    assert_eq!(block_on(future::poll_once(stream.next())), Some(Some(Some(Err(missing)))));
+    assert_eq!(block_on(future::poll_once(stream.next())), Some(None));
+}